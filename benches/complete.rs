@@ -64,8 +64,7 @@ macro_rules! get_day {
 }
 
 fn load_input(day: u8) -> String {
-    let path = format!("{}/inputs/day_{day}.txt", env!("CARGO_MANIFEST_DIR"));
-    std::fs::read_to_string(path).unwrap()
+    aoc::YEAR.load_input(day)
 }
 
 fn day_16(c: &mut Criterion) {
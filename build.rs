@@ -0,0 +1,21 @@
+use std::{env, fmt::Write, fs, path::Path};
+
+fn main() {
+    // the inputs as `(day, input)` pairs, only embedded when asked for so that other builds
+    // do not depend on them
+    let mut embedded = String::from("&[\n");
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_some() {
+        let inputs = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("inputs");
+        for day in 1..=25 {
+            let path = inputs.join(format!("day_{day}.txt"));
+            if path.exists() {
+                writeln!(embedded, "    ({day}, include_str!({:?})),", path).unwrap();
+            }
+        }
+        println!("cargo:rerun-if-changed={}", inputs.display());
+    }
+    embedded.push(']');
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("embedded.rs");
+    fs::write(out, embedded).unwrap();
+    println!("cargo:rerun-if-changed=build.rs");
+}
@@ -0,0 +1,261 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use serde_json::{json, Value};
+use tracing::warn;
+
+/// Environment variable holding the key, as 64 hex digits.
+pub const KEY_VARIABLE: &str = "AOC_BUNDLE_KEY";
+
+const MAGIC: &[u8; 8] = b"AOCBNDL1";
+const NONCE_LEN: usize = 12;
+
+/// Inputs and their expected answers, kept together so that they can be committed encrypted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Bundle {
+    pub inputs: BTreeMap<u8, String>,
+    pub answers: BTreeMap<u8, [String; 2]>,
+}
+
+#[derive(Debug)]
+pub enum BundleError {
+    NoKey,
+    BadKey,
+    NotABundle,
+    /// The key is wrong, or the bundle was changed after it was sealed.
+    Decrypt,
+    Malformed,
+    Io(io::Error),
+}
+
+impl Display for BundleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoKey => write!(f, "{KEY_VARIABLE} is not set"),
+            Self::BadKey => write!(f, "{KEY_VARIABLE} must be 64 hex digits"),
+            Self::NotABundle => write!(f, "not an input bundle"),
+            Self::Decrypt => write!(f, "the bundle does not open with this key"),
+            Self::Malformed => write!(f, "the bundle's contents are malformed"),
+            Self::Io(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for BundleError {}
+
+impl From<io::Error> for BundleError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// A new random key, as hex.
+pub fn generate_key() -> String {
+    ChaCha20Poly1305::generate_key(&mut OsRng)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// The key from the environment.
+pub fn key_from_env() -> Result<Key, BundleError> {
+    let hex = std::env::var(KEY_VARIABLE).map_err(|_| BundleError::NoKey)?;
+    parse_key(hex.trim())
+}
+
+fn parse_key(hex: &str) -> Result<Key, BundleError> {
+    if hex.len() != 64 || !hex.is_ascii() {
+        return Err(BundleError::BadKey);
+    }
+    let mut key = Key::default();
+    for (byte, digits) in key.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        let digits = std::str::from_utf8(digits).unwrap();
+        *byte = u8::from_str_radix(digits, 16).map_err(|_| BundleError::BadKey)?;
+    }
+    Ok(key)
+}
+
+/// Ties a bundle to its year, so that one year's bundle is not read as another's.
+fn associated_data(year: u16) -> Vec<u8> {
+    let mut data = MAGIC.to_vec();
+    data.extend(year.to_le_bytes());
+    data
+}
+
+impl Bundle {
+    /// Encrypts the bundle under the key.
+    pub fn seal(&self, key: &Key, year: u16) -> Vec<u8> {
+        let inputs: serde_json::Map<String, Value> = self
+            .inputs
+            .iter()
+            .map(|(day, input)| (day.to_string(), json!(input)))
+            .collect();
+        let answers: serde_json::Map<String, Value> = self
+            .answers
+            .iter()
+            .map(|(day, answers)| (day.to_string(), json!(answers)))
+            .collect();
+        let plaintext = json!({ "inputs": inputs, "answers": answers }).to_string();
+
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let aad = associated_data(year);
+        let ciphertext = ChaCha20Poly1305::new(key)
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext.as_bytes(),
+                    aad: &aad,
+                },
+            )
+            .expect("bundles fit in memory");
+        let mut sealed = MAGIC.to_vec();
+        sealed.extend_from_slice(&nonce);
+        sealed.extend(ciphertext);
+        sealed
+    }
+
+    pub fn open(sealed: &[u8], key: &Key, year: u16) -> Result<Self, BundleError> {
+        let rest = sealed.strip_prefix(MAGIC).ok_or(BundleError::NotABundle)?;
+        if rest.len() < NONCE_LEN {
+            return Err(BundleError::NotABundle);
+        }
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        let aad = associated_data(year);
+        let plaintext = ChaCha20Poly1305::new(key)
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| BundleError::Decrypt)?;
+
+        let contents: Value =
+            serde_json::from_slice(&plaintext).map_err(|_| BundleError::Malformed)?;
+        let mut bundle = Self::default();
+        let days = |name: &str| {
+            contents[name]
+                .as_object()
+                .into_iter()
+                .flatten()
+                .map(|(day, value)| Some((day.parse::<u8>().ok()?, value)))
+                .collect::<Option<Vec<_>>>()
+                .ok_or(BundleError::Malformed)
+        };
+        for (day, input) in days("inputs")? {
+            let input = input.as_str().ok_or(BundleError::Malformed)?;
+            bundle.inputs.insert(day, input.to_owned());
+        }
+        for (day, answers) in days("answers")? {
+            let answer = |part: usize| answers[part].as_str().map(String::from);
+            let (Some(part_1), Some(part_2)) = (answer(0), answer(1)) else {
+                return Err(BundleError::Malformed);
+            };
+            bundle.answers.insert(day, [part_1, part_2]);
+        }
+        Ok(bundle)
+    }
+
+    pub fn read(path: &Path, key: &Key, year: u16) -> Result<Self, BundleError> {
+        Self::open(&fs::read(path)?, key, year)
+    }
+
+    pub fn write(&self, path: &Path, key: &Key, year: u16) -> io::Result<()> {
+        fs::write(path, self.seal(key, year))
+    }
+}
+
+/// The bundle at `path`, opened once per process with the key from the environment.
+///
+/// `None` when there is no bundle or no key, so that inputs are read from disk instead.
+pub fn cached(path: &Path, year: u16) -> Option<Arc<Bundle>> {
+    static OPENED: OnceLock<Mutex<HashMap<PathBuf, Option<Arc<Bundle>>>>> = OnceLock::new();
+    let mut opened = OPENED.get_or_init(Default::default).lock().unwrap();
+    opened
+        .entry(path.to_owned())
+        .or_insert_with(|| {
+            if !path.exists() {
+                return None;
+            }
+            let opened = key_from_env().and_then(|key| Bundle::read(path, &key, year));
+            match opened {
+                Ok(bundle) => Some(Arc::new(bundle)),
+                Err(BundleError::NoKey) => None,
+                Err(error) => {
+                    warn!(%error, path = %path.display(), "ignoring input bundle");
+                    None
+                }
+            }
+        })
+        .clone()
+}
+
+#[cfg(test)]
+mod bundle_tests {
+    use super::*;
+
+    fn bundle() -> Bundle {
+        Bundle {
+            inputs: BTreeMap::from([
+                (1, String::from("1000\n2000\n")),
+                (10, String::from("noop\n")),
+            ]),
+            answers: BTreeMap::from([(1, [String::from("3000"), String::from("3000")])]),
+        }
+    }
+
+    #[test]
+    fn opens_what_was_sealed() {
+        let key = parse_key(&generate_key()).unwrap();
+        let sealed = bundle().seal(&key, 2022);
+        assert!(sealed.starts_with(MAGIC));
+        assert!(!sealed.windows(4).any(|window| window == b"noop"));
+        assert_eq!(Bundle::open(&sealed, &key, 2022).unwrap(), bundle());
+    }
+
+    #[test]
+    fn refuses_the_wrong_key_year_or_contents() {
+        let key = parse_key(&generate_key()).unwrap();
+        let other = parse_key(&generate_key()).unwrap();
+        let mut sealed = bundle().seal(&key, 2022);
+        assert!(matches!(
+            Bundle::open(&sealed, &other, 2022),
+            Err(BundleError::Decrypt)
+        ));
+        assert!(matches!(
+            Bundle::open(&sealed, &key, 2021),
+            Err(BundleError::Decrypt)
+        ));
+        let last = sealed.len() - 1;
+        sealed[last] ^= 1;
+        assert!(matches!(
+            Bundle::open(&sealed, &key, 2022),
+            Err(BundleError::Decrypt)
+        ));
+        assert!(matches!(
+            Bundle::open(b"plain text", &key, 2022),
+            Err(BundleError::NotABundle)
+        ));
+    }
+
+    #[test]
+    fn keys_are_hex() {
+        assert_eq!(generate_key().len(), 64);
+        assert!(parse_key(&"ab".repeat(32)).is_ok());
+        assert!(matches!(parse_key("abc"), Err(BundleError::BadKey)));
+        assert!(matches!(
+            parse_key(&"zz".repeat(32)),
+            Err(BundleError::BadKey)
+        ));
+    }
+}
@@ -0,0 +1,155 @@
+use std::{
+    fs, io,
+    path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Turns the cache off, so that every answer is computed again. Answers are still stored.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// What an answer depends on: the input, the puzzle, and the code that solved it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key<'a> {
+    pub input: &'a str,
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    /// Version of the crate solving the year.
+    pub crate_version: &'a str,
+    /// Version of the day's solver, bumped whenever its answers could change.
+    pub day_version: u32,
+}
+
+impl Key<'_> {
+    fn file_name(&self) -> String {
+        let mut hash = Fnv1a::default();
+        hash.write(self.input.as_bytes());
+        hash.write(&[0]);
+        hash.write(self.crate_version.as_bytes());
+        hash.write(&[0]);
+        hash.write(&self.day_version.to_le_bytes());
+        format!(
+            "{}_day_{:02}_part_{}_{:016x}.txt",
+            self.year,
+            self.day,
+            self.part,
+            hash.finish()
+        )
+    }
+}
+
+/// Answers stored on disk, one file per key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The stored answer, unless there is none or the cache is turned off.
+    pub fn get(&self, key: &Key) -> Option<String> {
+        if !is_enabled() {
+            return None;
+        }
+        fs::read_to_string(self.dir.join(key.file_name())).ok()
+    }
+
+    pub fn put(&self, key: &Key, answer: &str) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(key.file_name());
+        // written aside and renamed so that a reader never sees half an answer
+        let partial = path.with_extension(format!("{}.partial", std::process::id()));
+        fs::write(&partial, answer)?;
+        fs::rename(partial, path)
+    }
+}
+
+/// 64 bit FNV-1a, which is stable across builds unlike `DefaultHasher`.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod cache_tests {
+    use super::*;
+
+    fn key(input: &str) -> Key<'_> {
+        Key {
+            input,
+            year: 2022,
+            day: 16,
+            part: 2,
+            crate_version: "0.1.0",
+            day_version: 1,
+        }
+    }
+
+    #[test]
+    fn fnv_matches_reference() {
+        let mut hash = Fnv1a::default();
+        hash.write(b"a");
+        assert_eq!(hash.finish(), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn keys_change_with_everything_they_depend_on() {
+        let base = key("input");
+        let names = [
+            key("other input"),
+            Key { day: 15, ..base },
+            Key { part: 1, ..base },
+            Key {
+                crate_version: "0.2.0",
+                ..base
+            },
+            Key {
+                day_version: 2,
+                ..base
+            },
+        ]
+        .map(|key| key.file_name());
+        for name in names {
+            assert_ne!(name, base.file_name());
+        }
+        assert!(base.file_name().starts_with("2022_day_16_part_2_"));
+    }
+
+    #[test]
+    fn stores_answers() {
+        let dir = std::env::temp_dir().join(format!("aoc_cache_{}", std::process::id()));
+        let cache = Cache::new(&dir);
+        let key = key("stored");
+        assert_eq!(cache.get(&key), None);
+        cache.put(&key, "1707").unwrap();
+        assert_eq!(cache.get(&key).as_deref(), Some("1707"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
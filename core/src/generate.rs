@@ -0,0 +1,73 @@
+use std::ops::RangeInclusive;
+
+/// A small, seeded pseudo-random number generator (SplitMix64), so that a generated input can be
+/// made again from its seed.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, close enough to uniform for inputs.
+    ///
+    /// Panics if `range` is empty.
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        assert!(!range.is_empty(), "no number in an empty range");
+        match (range.end() - range.start()).checked_add(1) {
+            Some(span) => range.start() + self.next_u64() % span,
+            // every number is in range
+            None => self.next_u64(),
+        }
+    }
+
+    /// One of `items`, each as likely as the others.
+    ///
+    /// Panics if `items` is empty.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        assert!(!items.is_empty(), "nothing to pick from");
+        &items[self.range(0..=items.len() as u64 - 1) as usize]
+    }
+}
+
+#[cfg(test)]
+mod generate_tests {
+    use super::*;
+
+    #[test]
+    fn seeds_repeat() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..100).map(|_| rng.range(3..=7)).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(1), numbers(1));
+        assert_ne!(numbers(1), numbers(2));
+        assert!(numbers(1).iter().all(|n| (3..=7).contains(n)));
+        assert!((3..=7).all(|n| numbers(1).contains(&n)));
+    }
+
+    #[test]
+    fn takes_the_whole_range() {
+        let mut rng = Rng::new(1);
+        let numbers: Vec<u64> = (0..100).map(|_| rng.range(0..=u64::MAX)).collect();
+        assert!(numbers.iter().any(|&n| n > u64::MAX / 2));
+        assert_eq!(rng.range(u64::MAX..=u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "nothing to pick from")]
+    fn picks_from_something() {
+        Rng::new(1).pick::<u8>(&[]);
+    }
+}
@@ -0,0 +1,414 @@
+use std::{
+    fmt::{Display, Write as _},
+    fs::{self, OpenOptions},
+    io::{self, Write as _},
+    path::Path,
+    process::Command,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde_json::{json, Value};
+use tracing::warn;
+
+use crate::{
+    report::{timestamp, Machine},
+    theme::{Role, Theme},
+    DayRun, Year,
+};
+
+/// How many of a day's latest runs its trend is drawn from.
+const TREND_RUNS: usize = 10;
+
+/// Phases that slowed down by less than this are noise, however large the share.
+const NOISE: Duration = Duration::from_micros(100);
+
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Some of the days, run together at one revision on one machine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub year: u16,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// The git revision run, ending in `-dirty` when there were uncommitted changes.
+    pub revision: String,
+    pub machine: Machine,
+    pub runs: Vec<DayRun>,
+}
+
+impl Entry {
+    /// An entry for runs made just now, from the working tree at `dir`.
+    pub fn new(year: u16, runs: Vec<DayRun>, dir: &Path) -> Self {
+        Self {
+            year,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_secs()),
+            revision: revision(dir),
+            machine: Machine::current(),
+            runs,
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "year": self.year,
+            "timestamp": self.timestamp,
+            "revision": self.revision,
+            "machine": self.machine.to_json(),
+            "runs": self.runs.iter().map(DayRun::to_json).collect::<Vec<_>>(),
+        })
+    }
+
+    pub fn from_json(value: &Value) -> Option<Self> {
+        Some(Self {
+            year: value["year"].as_u64()?.try_into().ok()?,
+            timestamp: value["timestamp"].as_u64()?,
+            revision: value["revision"].as_str()?.to_owned(),
+            machine: Machine::from_json(&value["machine"])?,
+            runs: value["runs"]
+                .as_array()?
+                .iter()
+                .map(DayRun::from_json)
+                .collect::<Option<_>>()?,
+        })
+    }
+
+    fn run(&self, day: u8) -> Option<&DayRun> {
+        self.runs.iter().find(|run| run.day == day)
+    }
+}
+
+/// Appends the runs to the year's history.
+pub fn record(year: &Year, runs: Vec<DayRun>) -> io::Result<()> {
+    let path = Path::new(year.history);
+    let entry = Entry::new(year.year, runs, path.parent().unwrap_or(Path::new(".")));
+    append(path, &entry)
+}
+
+fn append(path: &Path, entry: &Entry) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", entry.to_json())
+}
+
+/// The year's entries in the history at `path`, oldest first.
+///
+/// Lines that cannot be read, such as one cut short by a crash, are skipped.
+pub fn read(path: &Path, year: u16) -> io::Result<Vec<Entry>> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error),
+    };
+    let mut entries = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let entry = serde_json::from_str(line)
+            .ok()
+            .and_then(|value| Entry::from_json(&value));
+        match entry {
+            Some(entry) if entry.year == year => entries.push(entry),
+            Some(_) => {}
+            None => warn!(line = index + 1, "skipping unreadable history entry"),
+        }
+    }
+    Ok(entries)
+}
+
+fn revision(dir: &Path) -> String {
+    Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .current_dir(dir)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|revision| revision.trim().to_owned())
+        .unwrap_or_else(|| String::from("unknown"))
+}
+
+/// Something about a day that differs from its previous run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// A phase took longer by more than the threshold.
+    Slower {
+        day: u8,
+        phase: &'static str,
+        before: Duration,
+        after: Duration,
+    },
+    Answer {
+        day: u8,
+        part: usize,
+        before: String,
+        after: String,
+    },
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Change::Slower {
+                day,
+                phase,
+                before,
+                after,
+            } => write!(
+                f,
+                "day {day} {phase} took {after:.2?}, {:+.1}% on {before:.2?}",
+                percent(*before, *after)
+            ),
+            Change::Answer {
+                day,
+                part,
+                before,
+                after,
+            } => write!(
+                f,
+                "day {day} part {} answered {}, was {}",
+                part + 1,
+                after.trim(),
+                before.trim()
+            ),
+        }
+    }
+}
+
+/// The runs of `day`, oldest first.
+fn runs(entries: &[Entry], day: u8) -> Vec<&DayRun> {
+    entries.iter().filter_map(|entry| entry.run(day)).collect()
+}
+
+/// How each day's latest run differs from the one before, flagging phases more than `threshold`
+/// slower, as a share of the time they took before.
+pub fn changes(entries: &[Entry], days: &[u8], threshold: f64) -> Vec<Change> {
+    let mut changes = Vec::new();
+    for &day in days {
+        let [.., before, after] = runs(entries, day)[..] else {
+            continue;
+        };
+        let mut phases = vec![("input", before.timings.input, after.timings.input)];
+        for part in 0..2 {
+            // a cached answer's time is only the cache lookup
+            if !before.cached[part] && !after.cached[part] {
+                let phase = if part == 0 { "part 1" } else { "part 2" };
+                phases.push((phase, before.timings.parts[part], after.timings.parts[part]));
+            }
+        }
+        for (phase, before, after) in phases {
+            if after > before + NOISE && percent(before, after) > threshold * 100.0 {
+                changes.push(Change::Slower {
+                    day,
+                    phase,
+                    before,
+                    after,
+                });
+            }
+        }
+        for part in 0..2 {
+            if before.answers[part].trim() != after.answers[part].trim() {
+                changes.push(Change::Answer {
+                    day,
+                    part,
+                    before: before.answers[part].clone(),
+                    after: after.answers[part].clone(),
+                });
+            }
+        }
+    }
+    changes
+}
+
+/// A row per day with its latest time and the trend over its last runs, followed by the changes
+/// since each day's previous run.
+pub fn render(theme: &Theme, entries: &[Entry], days: &[u8], threshold: f64) -> String {
+    let mut out = String::new();
+    let Some(latest) = entries.last() else {
+        writeln!(out, "{}", theme.paint(Role::Error, "no runs recorded yet")).unwrap();
+        return out;
+    };
+    let summary = format!(
+        "{} runs recorded, the latest of {} at {} on {}",
+        entries.len(),
+        latest.revision,
+        timestamp(latest.timestamp),
+        latest.machine.cpu
+    );
+    writeln!(out, "{summary}\n").unwrap();
+    let header = format!(
+        "day  runs  {:<TREND_RUNS$}  {:>10}  {:>10}  {:>7}",
+        "trend", "latest", "previous", "change"
+    );
+    writeln!(out, "{}", theme.paint(Role::Header, header)).unwrap();
+    for &day in days {
+        let runs = runs(entries, day);
+        let Some(last) = runs.last() else {
+            continue;
+        };
+        let totals: Vec<Duration> = runs.iter().map(|run| run.timings.total()).collect();
+        let trend = sparkline(&totals[totals.len().saturating_sub(TREND_RUNS)..]);
+        let (previous, change) = match totals[..] {
+            [.., before, after] => (
+                format!("{before:.2?}"),
+                format!("{:+.1}%", percent(before, after)),
+            ),
+            _ => (String::from("-"), String::from("-")),
+        };
+        write!(out, "{}", theme.paint(Role::Label, format!("{day:>3}"))).unwrap();
+        let row = format!(
+            "  {:>4}  {trend:<TREND_RUNS$}  {:>10}  {previous:>10}  {change:>7}",
+            runs.len(),
+            format!("{:.2?}", last.timings.total()),
+        );
+        writeln!(out, "{}", theme.paint(Role::Timing, row)).unwrap();
+    }
+    for change in changes(entries, days, threshold) {
+        writeln!(out, "{}", theme.paint(Role::Error, change.to_string())).unwrap();
+    }
+    out
+}
+
+/// How much longer `after` is than `before`, in percent.
+fn percent(before: Duration, after: Duration) -> f64 {
+    match before.as_secs_f64() {
+        before if before > 0.0 => (after.as_secs_f64() / before - 1.0) * 100.0,
+        _ => 0.0,
+    }
+}
+
+/// The times as bars from the shortest to the longest.
+fn sparkline(times: &[Duration]) -> String {
+    let (Some(low), Some(high)) = (times.iter().min(), times.iter().max()) else {
+        return String::new();
+    };
+    let range = (*high - *low).as_secs_f64();
+    times
+        .iter()
+        .map(|time| match range {
+            range if range > 0.0 => {
+                let share = (*time - *low).as_secs_f64() / range;
+                BARS[(share * (BARS.len() - 1) as f64).round() as usize]
+            }
+            _ => BARS[0],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod history_tests {
+    use super::*;
+    use crate::Timings;
+
+    fn run(day: u8, millis: [u64; 3], answers: [&str; 2]) -> DayRun {
+        DayRun {
+            day,
+            answers: answers.map(String::from),
+            cached: [false; 2],
+            timings: Timings {
+                input: Duration::from_millis(millis[0]),
+                parts: [
+                    Duration::from_millis(millis[1]),
+                    Duration::from_millis(millis[2]),
+                ],
+            },
+            allocations: None,
+        }
+    }
+
+    fn entry(revision: &str, runs: Vec<DayRun>) -> Entry {
+        Entry {
+            year: 2022,
+            timestamp: 1_671_000_000,
+            revision: String::from(revision),
+            machine: Machine {
+                os: String::from("linux"),
+                cpu: String::from("Test CPU"),
+                threads: 4,
+                rustc: String::from("rustc"),
+                profile: String::from("release"),
+            },
+            runs,
+        }
+    }
+
+    #[test]
+    fn appends_and_reads_entries() {
+        let dir = std::env::temp_dir().join(format!("aoc_history_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("history.jsonl");
+        assert_eq!(read(&path, 2022).unwrap(), vec![]);
+        let first = entry("abc1234", vec![run(1, [1, 2, 3], ["24000", "45000"])]);
+        let other_year = Entry {
+            year: 2021,
+            ..first.clone()
+        };
+        append(&path, &first).unwrap();
+        append(&path, &other_year).unwrap();
+        // as left by a run that was killed while writing
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{\"year\": 2022, \"times")
+            .unwrap();
+        let entries = read(&path, 2022).unwrap();
+        fs::remove_dir_all(dir).unwrap();
+        assert_eq!(entries, vec![first]);
+    }
+
+    #[test]
+    fn flags_regressions_and_changed_answers() {
+        let entries = [
+            entry("a", vec![run(1, [1, 10, 10], ["1", "2"])]),
+            entry("b", vec![run(2, [1, 1, 1], ["3", "4"])]),
+            entry("c", vec![run(1, [1, 12, 10], ["1", "5"])]),
+        ];
+        assert_eq!(
+            changes(&entries, &[1, 2], 0.1),
+            vec![
+                Change::Slower {
+                    day: 1,
+                    phase: "part 1",
+                    before: Duration::from_millis(10),
+                    after: Duration::from_millis(12),
+                },
+                Change::Answer {
+                    day: 1,
+                    part: 1,
+                    before: String::from("2"),
+                    after: String::from("5"),
+                },
+            ]
+        );
+        assert_eq!(changes(&entries, &[1], 0.25).len(), 1);
+        assert_eq!(
+            Change::Slower {
+                day: 1,
+                phase: "part 1",
+                before: Duration::from_millis(10),
+                after: Duration::from_millis(12),
+            }
+            .to_string(),
+            "day 1 part 1 took 12.00ms, +20.0% on 10.00ms"
+        );
+    }
+
+    #[test]
+    fn renders_trends() {
+        let entries = [
+            entry("a", vec![run(1, [0, 1, 1], ["1", "2"])]),
+            entry(
+                "b",
+                vec![run(1, [0, 2, 2], ["1", "2"]), run(2, [0, 1, 0], ["3", "4"])],
+            ),
+            entry("c", vec![run(1, [0, 1, 2], ["1", "2"])]),
+        ];
+        assert_eq!(
+            render(&Theme::MONOCHROME, &entries, &[1, 2, 3], 0.5),
+            "3 runs recorded, the latest of c at 2022-12-14T06:40:00Z on Test CPU\n\
+             \n\
+             day  runs  trend           latest    previous   change\n\
+             \x20 1     3  ▁█▅             3.00ms      4.00ms   -25.0%\n\
+             \x20 2     1  ▁               1.00ms           -        -\n"
+        );
+    }
+}
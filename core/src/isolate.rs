@@ -0,0 +1,339 @@
+use std::{
+    io::{self, Read},
+    path::Path,
+    process::{Child, Command, ExitStatus, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde_json::{json, Value};
+
+use crate::{
+    cache, print_answer,
+    theme::{paint, Role},
+    DayRun, Year,
+};
+
+/// The command a runner is re-executed with to solve a single day in a child process.
+pub const CHILD_COMMAND: &str = "solve-isolated";
+
+/// How often a running child is checked on.
+const POLL: Duration = Duration::from_millis(10);
+
+/// Lines of a failed child's standard error kept in its report.
+const STDERR_LINES: usize = 5;
+
+/// What a child process may use before it is stopped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// Bytes of address space, enforced with `RLIMIT_AS`.
+    pub memory: Option<u64>,
+    /// Wall-clock time, also enforced as CPU time with `RLIMIT_CPU`. Defaults to the day's budget.
+    pub time: Option<Duration>,
+}
+
+/// How solving a day in a child process went.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Solved(DayRun),
+    /// The child crashed, panicked, ran out of a limit or could not be started.
+    Failed(String),
+}
+
+/// Solves the day and writes the run to standard output as JSON, for the parent to read.
+///
+/// This is what a runner does when started with [`CHILD_COMMAND`].
+pub fn child(year: &Year, day: u8) {
+    let run = crate::solve_day(year, day);
+    println!("{}", run.to_json());
+}
+
+/// Solves one part of the day for the input in the file at `input`, without the cache, and
+/// writes the answer to standard output as JSON, for the parent to read.
+///
+/// This is what a runner does when started with [`CHILD_COMMAND`], `--part` and `--input`.
+pub fn child_part(year: &Year, day: u8, part: u8, input: &Path) {
+    let solver = year.get(day).expect("there is no solver for the day");
+    let input = std::fs::read_to_string(input).unwrap();
+    let solution = crate::solve_part(solver, part, &input).unwrap();
+    println!("{}", json!({ "answer": solution.answer }));
+}
+
+/// Solves the day in a copy of the current executable, started with [`CHILD_COMMAND`], so that a
+/// crash only takes down the copy.
+pub fn run(year: &Year, day: u8, limits: &Limits) -> Outcome {
+    let reported = spawn(year, day, &[], limits).and_then(|last| {
+        serde_json::from_str(&last)
+            .ok()
+            .and_then(|value| DayRun::from_json(&value))
+            .ok_or_else(|| String::from("the runner did not report its answers"))
+    });
+    match reported {
+        Ok(run) => Outcome::Solved(run),
+        Err(reason) => Outcome::Failed(reason),
+    }
+}
+
+/// Solves one part of the day for the input in the file at `input` in a copy of the current
+/// executable, like [`run`], never touching the cache. Returns the answer or why there is none.
+pub fn run_part(
+    year: &Year,
+    day: u8,
+    part: u8,
+    input: &Path,
+    limits: &Limits,
+) -> Result<String, String> {
+    let options = [
+        String::from("--part"),
+        part.to_string(),
+        String::from("--input"),
+        input.to_string_lossy().into_owned(),
+    ];
+    let last = spawn(year, day, &options, limits)?;
+    serde_json::from_str::<Value>(&last)
+        .ok()
+        .and_then(|value| Some(value["answer"].as_str()?.to_owned()))
+        .ok_or_else(|| String::from("the runner did not report its answer"))
+}
+
+/// Starts the runner on the day with `options`, returning the last line it printed if it exited
+/// successfully, or why it did not.
+fn spawn(year: &Year, day: u8, options: &[String], limits: &Limits) -> Result<String, String> {
+    let Some(solver) = year.get(day) else {
+        return Err(format!("there is no solver for day {day}"));
+    };
+    let time = limits.time.unwrap_or_else(|| solver.budget());
+    let exe =
+        std::env::current_exe().map_err(|error| format!("could not find the runner: {error}"))?;
+    let mut command = Command::new(exe);
+    command
+        .args(["--year", &year.year.to_string()])
+        .args([CHILD_COMMAND, &day.to_string()])
+        .args(options)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if !cache::is_enabled() {
+        command.arg("--no-cache");
+    }
+    limit(&mut command, limits.memory, time);
+    let mut child = command
+        .spawn()
+        .map_err(|error| format!("could not start the runner: {error}"))?;
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+    let waited = wait(&mut child, time);
+    let (stdout, stderr) = (stdout.join().unwrap(), stderr.join().unwrap());
+    match waited {
+        Err(error) => Err(format!("could not wait for the runner: {error}")),
+        Ok(None) => Err(format!("ran past the time limit of {time:?}")),
+        // solvers may print as they go, so only the last line is the report
+        Ok(Some(status)) if status.success() => Ok(stdout.lines().last().unwrap_or("").to_owned()),
+        Ok(Some(status)) => Err(describe(status, &stderr, limits.memory)),
+    }
+}
+
+/// Solves each day in its own child process, printing the answers or why the day failed, and
+/// carrying on with the rest either way. Returns how each day went.
+pub fn run_days(year: &Year, days: &[u8], limits: &Limits) -> Vec<Outcome> {
+    let mut outcomes = Vec::new();
+    for &day in days {
+        println!("{}", paint(Role::Header, format!("Day: {day}")));
+        let outcome = run(year, day, limits);
+        match &outcome {
+            Outcome::Solved(run) => {
+                for part in 0..2 {
+                    print_answer(part, &run.answers[part], run.cached[part]);
+                }
+            }
+            Outcome::Failed(reason) => {
+                println!("{}", paint(Role::Error, format!("failed: {reason}")));
+            }
+        }
+        println!("{}", paint(Role::Separator, "------------------"));
+        outcomes.push(outcome);
+    }
+    outcomes
+}
+
+/// Reads everything from the pipe on another thread, so that a chatty child never blocks on it.
+pub(crate) fn drain(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        if let Some(mut pipe) = pipe {
+            // whatever was read before the child died is still worth reporting
+            let _ = pipe.read_to_end(&mut bytes);
+        }
+        String::from_utf8_lossy(&bytes).into_owned()
+    })
+}
+
+/// Waits for the child to exit, killing it if it is still running after `time`.
+pub(crate) fn wait(child: &mut Child, time: Duration) -> io::Result<Option<ExitStatus>> {
+    let deadline = Instant::now() + time;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if Instant::now() >= deadline {
+            child.kill()?;
+            child.wait()?;
+            return Ok(None);
+        }
+        thread::sleep(POLL);
+    }
+}
+
+#[cfg(unix)]
+fn limit(command: &mut Command, memory: Option<u64>, time: Duration) {
+    use std::os::unix::process::CommandExt;
+
+    // a whole second more than the wall-clock limit, so that only a child spinning on every core
+    // hits it first
+    let cpu = time.as_secs() + 1;
+    // SAFETY: the closure only calls `setrlimit`, which is async-signal-safe
+    unsafe {
+        command.pre_exec(move || {
+            if let Some(bytes) = memory {
+                set_limit(libc::RLIMIT_AS, bytes)?;
+            }
+            set_limit(libc::RLIMIT_CPU, cpu)
+        });
+    }
+}
+
+#[cfg(not(unix))]
+fn limit(_command: &mut Command, memory: Option<u64>, _time: Duration) {
+    if memory.is_some() {
+        tracing::warn!("memory limits are only enforced on unix");
+    }
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+type Resource = libc::__rlimit_resource_t;
+#[cfg(all(unix, not(all(target_os = "linux", target_env = "gnu"))))]
+type Resource = libc::c_int;
+
+#[cfg(unix)]
+fn set_limit(resource: Resource, value: u64) -> io::Result<()> {
+    let limit = libc::rlimit {
+        rlim_cur: value as libc::rlim_t,
+        rlim_max: value as libc::rlim_t,
+    };
+    // SAFETY: `limit` is a valid `rlimit` for the duration of the call
+    match unsafe { libc::setrlimit(resource, &limit) } {
+        0 => Ok(()),
+        _ => Err(io::Error::last_os_error()),
+    }
+}
+
+/// Why a child that exited unsuccessfully did, from how it exited and what it last printed.
+fn describe(status: ExitStatus, stderr: &str, memory: Option<u64>) -> String {
+    let reason = if stderr.contains("has overflowed its stack") {
+        String::from("overflowed its stack")
+    } else if stderr.contains("memory allocation of") {
+        match memory {
+            Some(bytes) => format!("ran out of memory, limited to {} MiB", bytes >> 20),
+            None => String::from("ran out of memory"),
+        }
+    } else if let Some(message) = panic_of(stderr) {
+        format!("panicked: {message}")
+    } else {
+        match signal(status) {
+            Some(signal) => format!("killed by {}", signal_name(signal)),
+            None => format!("exited with {status}"),
+        }
+    };
+    let tail: Vec<&str> = stderr.trim().lines().rev().take(STDERR_LINES).collect();
+    if tail.is_empty() || reason.starts_with("panicked") {
+        reason
+    } else {
+        let tail: Vec<&str> = tail.into_iter().rev().collect();
+        format!("{reason}\n{}", tail.join("\n"))
+    }
+}
+
+/// The message of the first panic in `stderr`, which follows the line saying where it happened.
+fn panic_of(stderr: &str) -> Option<&str> {
+    let mut lines = stderr.lines();
+    lines.find(|line| line.contains("panicked at"))?;
+    lines.next().map(str::trim)
+}
+
+#[cfg(unix)]
+fn signal(status: ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+
+    status.signal()
+}
+
+#[cfg(not(unix))]
+fn signal(_status: ExitStatus) -> Option<i32> {
+    None
+}
+
+fn signal_name(signal: i32) -> String {
+    #[cfg(unix)]
+    let name = match signal {
+        libc::SIGSEGV => Some("SIGSEGV, likely a stack overflow"),
+        libc::SIGABRT => Some("SIGABRT"),
+        libc::SIGKILL => Some("SIGKILL"),
+        libc::SIGXCPU => Some("SIGXCPU, past the CPU time limit"),
+        libc::SIGBUS => Some("SIGBUS"),
+        libc::SIGILL => Some("SIGILL"),
+        libc::SIGFPE => Some("SIGFPE"),
+        _ => None,
+    };
+    #[cfg(not(unix))]
+    let name: Option<&str> = None;
+    name.map_or_else(|| format!("signal {signal}"), String::from)
+}
+
+#[cfg(test)]
+mod isolate_tests {
+    use super::*;
+
+    #[test]
+    fn finds_panic_messages() {
+        let stderr = "thread 'main' panicked at src/days/day_13.rs:40:14:\n\
+                      called `Option::unwrap()` on a `None` value\n\
+                      note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace\n";
+        assert_eq!(
+            panic_of(stderr),
+            Some("called `Option::unwrap()` on a `None` value")
+        );
+        assert_eq!(panic_of("fatal runtime error: stack overflow\n"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn describes_crashes() {
+        use std::os::unix::process::ExitStatusExt;
+
+        let aborted = ExitStatus::from_raw(libc::SIGABRT);
+        let overflow =
+            "\nthread 'main' has overflowed its stack\nfatal runtime error: stack overflow\n";
+        assert_eq!(
+            describe(aborted, overflow, None),
+            "overflowed its stack\n\
+             thread 'main' has overflowed its stack\n\
+             fatal runtime error: stack overflow"
+        );
+        assert_eq!(
+            describe(
+                aborted,
+                "memory allocation of 4096 bytes failed\n",
+                Some(64 << 20)
+            ),
+            "ran out of memory, limited to 64 MiB\nmemory allocation of 4096 bytes failed"
+        );
+        let killed = ExitStatus::from_raw(libc::SIGSEGV);
+        assert_eq!(
+            describe(killed, "", None),
+            "killed by SIGSEGV, likely a stack overflow"
+        );
+        let panicked = ExitStatus::from_raw(101 << 8);
+        let stderr = "thread 'main' panicked at src/days/day_21.rs:9:5:\nno root\n";
+        assert_eq!(describe(panicked, stderr, None), "panicked: no root");
+    }
+}
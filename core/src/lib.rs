@@ -0,0 +1,675 @@
+use std::{
+    fs::File,
+    io,
+    io::BufWriter,
+    panic,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Once,
+    },
+    time::{Duration, Instant},
+};
+
+use alloc::AllocStats;
+use cache::{Cache, Key};
+use theme::{paint, Role};
+use tracing::{debug, info_span, warn};
+use visualize::{
+    export::{self, ExportOptions},
+    Frame, Visualize,
+};
+
+pub mod alloc;
+pub mod bundle;
+pub mod cache;
+pub mod generate;
+pub mod history;
+pub mod isolate;
+pub mod lint;
+pub mod logging;
+pub mod matrix;
+pub mod minimize;
+pub mod oracle;
+pub mod report;
+pub mod serve;
+pub mod snapshot;
+pub mod stream;
+pub mod theme;
+pub mod visualize;
+pub mod watch;
+
+pub trait Day: Sync {
+    fn part_1(&self, input: &str) -> String;
+    fn part_2(&self, input: &str) -> String;
+
+    /// The version of the day's answers, part of the key they are cached under along with the
+    /// crate's version.
+    ///
+    /// Starts at 1. A solver must bump it, overriding this, whenever a change could alter its
+    /// answers, as the cache otherwise keeps serving the answers from before the change.
+    fn version(&self) -> u32 {
+        1
+    }
+
+    /// How long the solver may take on a part before a caller gives up on it.
+    fn budget(&self) -> Duration {
+        Duration::from_secs(30)
+    }
+
+    /// The visualization of the day's simulation, if it has one.
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        None
+    }
+
+    /// The solver that reads the input as a stream, if the day has one.
+    fn streaming(&self) -> Option<&dyn stream::Stream> {
+        None
+    }
+
+    /// Checks the input against the day's format, if the day describes one.
+    fn lint(&self, _input: &str) -> Option<Vec<lint::Problem>> {
+        None
+    }
+
+    /// A random input in the day's format, if the day can make them.
+    fn generate(&self, _rng: &mut generate::Rng) -> Option<String> {
+        None
+    }
+}
+
+/// A year of puzzles, registered by the crate or module that solves them.
+#[derive(Clone, Copy)]
+pub struct Year {
+    pub year: u16,
+    /// Looks up the solver for a day.
+    pub get: fn(u8) -> Option<&'static dyn Day>,
+    /// Directory holding the inputs, named `day_{day}.txt`.
+    pub inputs: &'static str,
+    /// Version of the crate solving the year, part of the key for cached answers.
+    pub version: &'static str,
+    /// Directory where answers are cached.
+    pub cache: &'static str,
+    /// Encrypted bundle of inputs and expected answers, read instead of the inputs directory
+    /// when it exists and the key is set.
+    pub bundle: &'static str,
+    /// JSON lines file that every run is appended to, to follow the timings over time.
+    pub history: &'static str,
+    /// Inputs compiled into the binary, read before any others.
+    pub embedded: &'static [(u8, &'static str)],
+}
+
+impl Year {
+    pub fn get(&self, day: u8) -> Option<&'static dyn Day> {
+        (self.get)(day)
+    }
+
+    /// Solves one part of a day for the input, without touching the cache.
+    pub fn solve(&self, day: u8, part: u8, input: &str) -> Result<Solution, SolveError> {
+        let solver = self.get(day).ok_or(SolveError::NoSuchDay(day))?;
+        solve_part(solver, part, input)
+    }
+
+    /// The days that have a solver.
+    pub fn days(&self) -> Vec<u8> {
+        (1..=25).filter(|&day| self.get(day).is_some()).collect()
+    }
+
+    pub fn input_path(&self, day: u8) -> PathBuf {
+        Path::new(self.inputs).join(format!("day_{day}.txt"))
+    }
+
+    /// The day's input, from the binary or the bundle when either has it.
+    pub fn read_input(&self, day: u8) -> io::Result<String> {
+        if let Some(&(_, input)) = self.embedded.iter().find(|&&(embedded, _)| embedded == day) {
+            return Ok(input.to_owned());
+        }
+        let bundled = bundle::cached(Path::new(self.bundle), self.year)
+            .and_then(|bundle| bundle.inputs.get(&day).cloned());
+        match bundled {
+            Some(input) => Ok(input),
+            None => std::fs::read_to_string(self.input_path(day)),
+        }
+    }
+
+    pub fn load_input(&self, day: u8) -> String {
+        self.read_input(day).unwrap()
+    }
+
+    /// The answers recorded in the bundle for the day.
+    pub fn expected_answers(&self, day: u8) -> Option<[String; 2]> {
+        bundle::cached(Path::new(self.bundle), self.year)?
+            .answers
+            .get(&day)
+            .cloned()
+    }
+}
+
+/// An answer and how long it took to compute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    pub answer: String,
+    pub time: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveError {
+    NoSuchDay(u8),
+    NoSuchPart(u8),
+}
+
+impl std::fmt::Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoSuchDay(day) => write!(f, "there is no solver for day {day}"),
+            Self::NoSuchPart(part) => write!(f, "there is no part {part}, only 1 and 2"),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// Solves one part with the solver, timing it.
+pub fn solve_part(solver: &dyn Day, part: u8, input: &str) -> Result<Solution, SolveError> {
+    let start = Instant::now();
+    let answer = match part {
+        1 => solver.part_1(input),
+        2 => solver.part_2(input),
+        _ => return Err(SolveError::NoSuchPart(part)),
+    };
+    Ok(Solution {
+        answer,
+        time: start.elapsed(),
+    })
+}
+
+/// Finds the year among those registered.
+pub fn find_year<'a>(years: &[&'a Year], year: u16) -> Option<&'a Year> {
+    years
+        .iter()
+        .find(|registered| registered.year == year)
+        .copied()
+}
+
+/// Time spent in each phase of solving a day.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timings {
+    pub input: Duration,
+    pub parts: [Duration; 2],
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.input + self.parts[0] + self.parts[1]
+    }
+}
+
+/// Answers and timings from solving both parts of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayRun {
+    pub day: u8,
+    pub answers: [String; 2],
+    /// Whether each answer came from the cache rather than being solved.
+    pub cached: [bool; 2],
+    pub timings: Timings,
+    /// Allocations made by each part, when the counting allocator is installed.
+    pub allocations: Option<[AllocStats; 2]>,
+}
+
+impl DayRun {
+    /// The run as a JSON object, leaving out the allocations.
+    pub fn to_json(&self) -> serde_json::Value {
+        let nanos = |time: Duration| time.as_nanos() as u64;
+        serde_json::json!({
+            "day": self.day,
+            "answers": self.answers,
+            "cached": self.cached,
+            "input_ns": nanos(self.timings.input),
+            "parts_ns": self.timings.parts.map(nanos),
+        })
+    }
+
+    pub fn from_json(value: &serde_json::Value) -> Option<Self> {
+        let answer = |part: usize| value["answers"][part].as_str().map(String::from);
+        let cached = |part: usize| value["cached"][part].as_bool();
+        let time = |value: &serde_json::Value| value.as_u64().map(Duration::from_nanos);
+        Some(Self {
+            day: value["day"].as_u64()?.try_into().ok()?,
+            answers: [answer(0)?, answer(1)?],
+            cached: [cached(0)?, cached(1)?],
+            timings: Timings {
+                input: time(&value["input_ns"])?,
+                parts: [time(&value["parts_ns"][0])?, time(&value["parts_ns"][1])?],
+            },
+            allocations: None,
+        })
+    }
+}
+
+/// Loads the day's input and solves both parts.
+pub fn solve_day(year: &Year, day: u8) -> DayRun {
+    solve(year, day, &|| year.read_input(day), &mut |_, _, _| ()).unwrap()
+}
+
+/// Solves both parts for the input in the file at `path`.
+pub fn solve_file(year: &Year, day: u8, path: &Path) -> io::Result<DayRun> {
+    solve(
+        year,
+        day,
+        &|| std::fs::read_to_string(path),
+        &mut |_, _, _| (),
+    )
+}
+
+/// Solves both parts, handing each answer to `on_answer` as soon as it is known.
+///
+/// Answers are taken from the cache when possible, and stored there otherwise.
+fn solve(
+    year: &Year,
+    day: u8,
+    load: &dyn Fn() -> io::Result<String>,
+    on_answer: &mut dyn FnMut(usize, &str, bool),
+) -> io::Result<DayRun> {
+    let _span = info_span!("day", year = year.year, day).entered();
+    let start = Instant::now();
+    let input = info_span!("input").in_scope(load)?;
+    let input_time = start.elapsed();
+    debug!(bytes = input.len(), "loaded input");
+
+    let solver = year.get(day).unwrap();
+    let cache = Cache::new(year.cache);
+    let mut answers: [String; 2] = Default::default();
+    let mut cached = [false; 2];
+    let mut parts = [Duration::ZERO; 2];
+    let mut allocations = [AllocStats::default(); 2];
+    for (part, answer) in answers.iter_mut().enumerate() {
+        let key = Key {
+            input: &input,
+            year: year.year,
+            day,
+            part: part as u8 + 1,
+            crate_version: year.version,
+            day_version: solver.version(),
+        };
+        let _span = info_span!("part", part = part + 1).entered();
+        let before = alloc::snapshot();
+        let start = Instant::now();
+        match cache.get(&key) {
+            Some(hit) => {
+                debug!("answer found in cache");
+                *answer = hit;
+                cached[part] = true;
+            }
+            None => {
+                *answer = if part == 0 {
+                    solver.part_1(&input)
+                } else {
+                    solver.part_2(&input)
+                };
+            }
+        }
+        parts[part] = start.elapsed();
+        allocations[part] = alloc::snapshot() - before;
+        if !cached[part] {
+            // a cache that cannot be written to only costs the next run time
+            if let Err(error) = cache.put(&key, answer) {
+                warn!(%error, "could not cache answer");
+            }
+        }
+        on_answer(part, answer, cached[part]);
+    }
+
+    Ok(DayRun {
+        day,
+        answers,
+        cached,
+        timings: Timings {
+            input: input_time,
+            parts,
+        },
+        allocations: alloc::is_counting().then_some(allocations),
+    })
+}
+
+/// Solves the day, printing each answer as soon as it is known.
+pub fn run_day(year: &Year, day: u8) -> DayRun {
+    println!("{}", paint(Role::Header, format!("Day: {day}")));
+
+    let run = solve(
+        year,
+        day,
+        &|| year.read_input(day),
+        &mut |part, answer, cached| print_answer(part, answer, cached),
+    )
+    .unwrap();
+
+    println!("{}", paint(Role::Separator, "------------------"));
+    run
+}
+
+/// Prints a part's answer the way `run_day` does.
+pub(crate) fn print_answer(part: usize, answer: &str, cached: bool) {
+    if part == 1 {
+        println!();
+    }
+    let label = if cached {
+        format!("- Part {} (cached):", part + 1)
+    } else {
+        format!("- Part {}:", part + 1)
+    };
+    println!("{}", paint(Role::Label, label));
+    println!("{}", paint(Role::Answer, answer));
+}
+
+/// How many [`QuietPanics`] are alive.
+static QUIET: AtomicUsize = AtomicUsize::new(0);
+
+/// Keeps panics from being printed for as long as it is alive, for code that catches and reports
+/// them itself. Panics on any thread are kept quiet, including those of threads it started.
+pub(crate) struct QuietPanics(());
+
+impl QuietPanics {
+    pub(crate) fn new() -> Self {
+        // the hook is only swapped once, so that guards dropped in any order, or on a panic, can
+        // never leave the wrong one in place
+        static HOOK: Once = Once::new();
+        HOOK.call_once(|| {
+            let hook = panic::take_hook();
+            panic::set_hook(Box::new(move |info| {
+                if QUIET.load(Ordering::SeqCst) == 0 {
+                    hook(info);
+                }
+            }));
+        });
+        QUIET.fetch_add(1, Ordering::SeqCst);
+        Self(())
+    }
+}
+
+impl Drop for QuietPanics {
+    fn drop(&mut self) {
+        QUIET.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// The message a panic was raised with, if it had one.
+pub(crate) fn panic_message(payload: &Box<dyn std::any::Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_default()
+}
+
+/// Runs the days and writes a report on them to `path`.
+pub fn report_days(
+    year: &Year,
+    days: &[u8],
+    format: report::Format,
+    path: &Path,
+) -> io::Result<()> {
+    let runs = days.iter().map(|&day| solve_day(year, day)).collect();
+    let report = report::Report::new(year.year, runs);
+    std::fs::write(path, report.render(format))
+}
+
+/// Bundles the days' inputs from the inputs directory with the answers they solve to.
+///
+/// Answers come from the cache while it is enabled, so turn it off for answers to trust.
+pub fn bundle_days(year: &Year, days: &[u8]) -> io::Result<bundle::Bundle> {
+    let mut bundle = bundle::Bundle::default();
+    for &day in days {
+        let path = year.input_path(day);
+        let in_path =
+            |error: io::Error| io::Error::new(error.kind(), format!("{}: {error}", path.display()));
+        let run = solve_file(year, day, &path).map_err(in_path)?;
+        let input = std::fs::read_to_string(&path).map_err(in_path)?;
+        bundle.inputs.insert(day, input);
+        bundle.answers.insert(day, run.answers);
+    }
+    Ok(bundle)
+}
+
+/// Solves the days, comparing against the answers in the bundle. Returns whether all matched.
+///
+/// Answers come from the cache while it is enabled, so turn it off for a check to mean anything.
+pub fn check_days(year: &Year, days: &[u8]) -> bool {
+    let mut all_match = true;
+    for &day in days {
+        let Some(expected) = year.expected_answers(day) else {
+            println!(
+                "{}",
+                paint(Role::Error, format!("Day {day}: no expected answers"))
+            );
+            all_match = false;
+            continue;
+        };
+        let run = solve_day(year, day);
+        for (part, (answer, expected)) in run.answers.iter().zip(&expected).enumerate() {
+            let label = format!("Day {day} part {}:", part + 1);
+            if answer == expected {
+                println!(
+                    "{} {}",
+                    paint(Role::Label, label),
+                    paint(Role::Answer, "ok")
+                );
+            } else {
+                let mismatch = format!("got {answer}, expected {expected}");
+                println!(
+                    "{} {}",
+                    paint(Role::Label, label),
+                    paint(Role::Error, mismatch)
+                );
+                all_match = false;
+            }
+        }
+    }
+    all_match
+}
+
+/// Plays the day's simulation in the terminal with playback controls, starting `delay` apart.
+pub fn visualize_day(year: &Year, day: u8, delay: Duration) {
+    let input = year.load_input(day);
+    let visualizer = year.get(day).and_then(|solver| solver.visualizer());
+    match visualizer {
+        Some(visualizer) => visualize::player::play(visualizer, &input, delay).unwrap(),
+        None => eprintln!(
+            "{}",
+            paint(Role::Error, format!("Day {day} has no visualization"))
+        ),
+    }
+}
+
+/// Records the day's simulation to `path`.
+///
+/// The format follows the extension: `.cast` for asciinema, `.gif`, and otherwise a directory of
+/// numbered PNGs.
+pub fn export_day(year: &Year, day: u8, path: &Path, options: &ExportOptions) -> io::Result<()> {
+    let input = year.load_input(day);
+    let visualizer = year
+        .get(day)
+        .and_then(|solver| solver.visualizer())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("day {day} has no visualization"),
+            )
+        })?;
+    let mut frames: Vec<Frame> = Vec::new();
+    visualizer.visualize(&input, &mut frames);
+
+    let create = || -> io::Result<BufWriter<File>> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        Ok(BufWriter::new(File::create(path)?))
+    };
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("cast") => export::write_asciicast(&frames, options, create()?),
+        Some("gif") => export::write_gif(&frames, options, create()?),
+        _ => export::write_png_sequence(&frames, options, path).map(|_| ()),
+    }
+}
+
+#[cfg(test)]
+mod core_tests {
+    use super::*;
+
+    struct Echo;
+
+    impl Day for Echo {
+        fn part_1(&self, input: &str) -> String {
+            input.trim().to_owned()
+        }
+
+        fn part_2(&self, input: &str) -> String {
+            input.trim().len().to_string()
+        }
+    }
+
+    fn get(day: u8) -> Option<&'static dyn Day> {
+        (day == 3).then_some(&Echo)
+    }
+
+    #[test]
+    fn years_are_found_by_number() {
+        let first = Year {
+            year: 2021,
+            get,
+            inputs: "",
+            version: "0.1.0",
+            cache: "",
+            bundle: "",
+            history: "",
+            embedded: &[],
+        };
+        let second = Year {
+            year: 2022,
+            ..first
+        };
+        assert_eq!(find_year(&[&first, &second], 2022).unwrap().year, 2022);
+        assert!(find_year(&[&first], 2023).is_none());
+        assert_eq!(first.days(), vec![3]);
+    }
+
+    #[test]
+    fn solves_from_the_year_inputs_and_then_the_cache() {
+        let dir = std::env::temp_dir().join(format!("aoc_core_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("day_3.txt"), "hello\n").unwrap();
+        let dir: &'static str = Box::leak(dir.to_string_lossy().into_owned().into_boxed_str());
+        let year = Year {
+            year: 2022,
+            get,
+            inputs: dir,
+            version: "0.1.0",
+            cache: Box::leak(format!("{dir}/cache").into_boxed_str()),
+            bundle: "",
+            history: "",
+            embedded: &[],
+        };
+        let run = solve_day(&year, 3);
+        assert_eq!(run.answers, [String::from("hello"), String::from("5")]);
+        assert_eq!(run.cached, [false, false]);
+        let run = solve_day(&year, 3);
+        assert_eq!(run.answers, [String::from("hello"), String::from("5")]);
+        assert_eq!(run.cached, [true, true]);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn new_day_versions_miss_the_cache() {
+        struct Fixed;
+
+        impl Day for Fixed {
+            fn part_1(&self, input: &str) -> String {
+                Echo.part_1(input)
+            }
+
+            fn part_2(&self, input: &str) -> String {
+                Echo.part_2(input)
+            }
+
+            fn version(&self) -> u32 {
+                2
+            }
+        }
+
+        let dir = std::env::temp_dir().join(format!("aoc_core_version_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("day_3.txt"), "hello\n").unwrap();
+        let dir: &'static str = Box::leak(dir.to_string_lossy().into_owned().into_boxed_str());
+        let year = Year {
+            year: 2022,
+            get,
+            inputs: dir,
+            version: "0.1.0",
+            cache: Box::leak(format!("{dir}/cache").into_boxed_str()),
+            bundle: "",
+            history: "",
+            embedded: &[],
+        };
+        let fixed = Year {
+            get: |day| (day == 3).then_some(&Fixed as &dyn Day),
+            ..year
+        };
+        assert_eq!(solve_day(&year, 3).cached, [false, false]);
+        assert_eq!(solve_day(&fixed, 3).cached, [false, false]);
+        assert_eq!(solve_day(&fixed, 3).cached, [true, true]);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn bundling_names_missing_inputs() {
+        let year = Year {
+            year: 2022,
+            get,
+            inputs: "/nonexistent/inputs",
+            version: "0.1.0",
+            cache: "",
+            bundle: "",
+            history: "",
+            embedded: &[],
+        };
+        let error = bundle_days(&year, &[3]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        assert!(error
+            .to_string()
+            .starts_with("/nonexistent/inputs/day_3.txt: "));
+    }
+
+    #[test]
+    fn embedded_inputs_come_first() {
+        let year = Year {
+            year: 2022,
+            get,
+            inputs: "/nonexistent",
+            version: "0.1.0",
+            cache: "",
+            bundle: "",
+            history: "",
+            embedded: &[(3, "compiled in\n")],
+        };
+        assert_eq!(year.read_input(3).unwrap(), "compiled in\n");
+        assert!(year.read_input(4).is_err());
+    }
+
+    #[test]
+    fn runs_round_trip_through_json() {
+        let run = DayRun {
+            day: 10,
+            answers: [String::from("13140"), String::from("##..\n#..#\n")],
+            cached: [true, false],
+            timings: Timings {
+                input: Duration::from_micros(12),
+                parts: [Duration::from_nanos(345), Duration::from_millis(6)],
+            },
+            allocations: None,
+        };
+        let line = run.to_json().to_string();
+        assert!(!line.contains('\n'));
+        let value = serde_json::from_str(&line).unwrap();
+        assert_eq!(DayRun::from_json(&value), Some(run));
+        assert_eq!(DayRun::from_json(&serde_json::json!({ "day": 1 })), None);
+    }
+}
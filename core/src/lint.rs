@@ -0,0 +1,308 @@
+use std::{collections::BTreeSet, fmt::Display};
+
+use crate::Year;
+
+/// Something wrong with an input, at a 1-based line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    pub line: usize,
+    pub message: String,
+}
+
+impl Problem {
+    pub fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Checks every line of `text`, which starts at line `first`, with `rule`.
+pub fn lines(
+    first: usize,
+    text: &str,
+    mut rule: impl FnMut(&str) -> Result<(), String>,
+) -> Vec<Problem> {
+    if text.trim().is_empty() {
+        return vec![Problem::new(first, "expected some lines, found none")];
+    }
+    text.lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            rule(line)
+                .err()
+                .map(|error| Problem::new(first + index, error))
+        })
+        .collect()
+}
+
+/// The sections of `input` separated by blank lines, with the line each starts at.
+pub fn blocks(input: &str) -> Vec<(usize, &str)> {
+    let mut blocks = Vec::new();
+    let mut line = 1;
+    for block in input.split("\n\n") {
+        blocks.push((line, block));
+        line += block.matches('\n').count() + 2;
+    }
+    blocks
+}
+
+/// Checks that `text`, starting at line `first`, is a rectangle of the `allowed` characters.
+pub fn grid(first: usize, text: &str, allowed: &str) -> Vec<Problem> {
+    let width = text.lines().next().map_or(0, |row| row.chars().count());
+    lines(first, text, |row| {
+        if let Some(tile) = row.chars().find(|&tile| !allowed.contains(tile)) {
+            return Err(format!("unexpected {tile:?}, expected one of `{allowed}`"));
+        }
+        match row.chars().count() {
+            len if len == width => Ok(()),
+            len => Err(format!(
+                "row is {len} wide, but the first row is {width} wide"
+            )),
+        }
+    })
+}
+
+/// Checks that `text`, starting at line `first`, is a single line of the `allowed` characters.
+pub fn single_line(first: usize, text: &str, allowed: &str) -> Vec<Problem> {
+    let mut problems = lines(first, text, |line| {
+        match line.chars().find(|&c| !allowed.contains(c)) {
+            Some(c) => Err(format!("unexpected {c:?}, expected one of `{allowed}`")),
+            None => Ok(()),
+        }
+    });
+    if text.trim_end_matches('\n').lines().count() > 1 {
+        problems.push(Problem::new(first + 1, "expected a single line"));
+    }
+    problems
+}
+
+/// Matches `line` against `template`, returning the text of each placeholder.
+///
+/// `{n}` is a number, `{i}` a number that may be negative, `{w}` a word of letters and digits.
+/// Everything else must appear as written.
+pub fn scan<'a>(line: &'a str, template: &str) -> Result<Vec<&'a str>, String> {
+    let mut fields = Vec::new();
+    let mut rest = line;
+    let mut template = template;
+    while !template.is_empty() {
+        let literal_end = template.find('{').unwrap_or(template.len());
+        let (literal, after) = template.split_at(literal_end);
+        rest = rest
+            .strip_prefix(literal)
+            .ok_or_else(|| format!("expected `{literal}`, found `{}`", snippet(rest)))?;
+        template = after;
+        if template.is_empty() {
+            break;
+        }
+        let (kind, after) = (&template[1..2], &template[3..]);
+        let run = |text: &str, accept: fn(char) -> bool| {
+            text.find(|c: char| !accept(c)).unwrap_or(text.len())
+        };
+        let len = match kind {
+            "n" => run(rest, |c| c.is_ascii_digit()),
+            "i" => {
+                let sign = usize::from(rest.starts_with('-'));
+                match run(&rest[sign..], |c| c.is_ascii_digit()) {
+                    0 => 0,
+                    digits => sign + digits,
+                }
+            }
+            _ => run(rest, |c| c.is_ascii_alphanumeric()),
+        };
+        if len == 0 {
+            let expected = if kind == "w" { "a word" } else { "a number" };
+            return Err(format!("expected {expected}, found `{}`", snippet(rest)));
+        }
+        fields.push(&rest[..len]);
+        rest = &rest[len..];
+        template = after;
+    }
+    if !rest.is_empty() {
+        return Err(format!("unexpected `{}` at the end", snippet(rest)));
+    }
+    Ok(fields)
+}
+
+/// A day whose format an input could be in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Candidate {
+    pub day: u8,
+    /// Share of the input's lines that the day's format accepts.
+    pub accepted: f64,
+    /// How likely the input is to be this day's rather than another's, out of 1.
+    pub confidence: f64,
+}
+
+/// The days whose formats accept at least some of the input, most likely first.
+pub fn identify(year: &Year, input: &str) -> Vec<Candidate> {
+    let total = input.lines().count().max(1);
+    let mut candidates: Vec<Candidate> = year
+        .days()
+        .into_iter()
+        .filter_map(|day| {
+            let problems = year.get(day)?.lint(input)?;
+            let rejected: BTreeSet<usize> = problems.iter().map(|problem| problem.line).collect();
+            let accepted = 1.0 - rejected.len().min(total) as f64 / total as f64;
+            Some(Candidate {
+                day,
+                accepted,
+                confidence: 0.0,
+            })
+        })
+        .filter(|candidate| candidate.accepted > 0.0)
+        .collect();
+    // sharpened so that a format accepting every line far outweighs one that accepts most
+    let weight = |candidate: &Candidate| candidate.accepted.powi(8);
+    let sum: f64 = candidates.iter().map(weight).sum();
+    for candidate in &mut candidates {
+        candidate.confidence = weight(candidate) / sum;
+    }
+    candidates.sort_by(|a, b| {
+        b.confidence
+            .total_cmp(&a.confidence)
+            .then(a.day.cmp(&b.day))
+    });
+    candidates
+}
+
+/// The start of `text`, for error messages.
+fn snippet(text: &str) -> &str {
+    match text.char_indices().nth(12) {
+        Some((end, _)) => &text[..end],
+        None => text,
+    }
+}
+
+#[cfg(test)]
+mod lint_tests {
+    use super::*;
+    use crate::Day;
+
+    struct Digits;
+
+    impl Day for Digits {
+        fn part_1(&self, _input: &str) -> String {
+            String::new()
+        }
+
+        fn part_2(&self, _input: &str) -> String {
+            String::new()
+        }
+
+        fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+            Some(grid(1, input, "0123456789"))
+        }
+    }
+
+    struct Moves;
+
+    impl Day for Moves {
+        fn part_1(&self, _input: &str) -> String {
+            String::new()
+        }
+
+        fn part_2(&self, _input: &str) -> String {
+            String::new()
+        }
+
+        fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+            Some(lines(1, input, |line| scan(line, "{w} {n}").map(drop)))
+        }
+    }
+
+    static YEAR: Year = Year {
+        year: 2022,
+        get: |day| match day {
+            1 => Some(&Digits),
+            2 => Some(&Moves),
+            3 => Some(&Digits as &dyn Day),
+            _ => None,
+        },
+        inputs: "",
+        version: "0.1.0",
+        cache: "",
+        bundle: "",
+        history: "",
+        embedded: &[],
+    };
+
+    #[test]
+    fn scans_templates() {
+        assert_eq!(
+            scan("move 1 from 2 to 13", "move {n} from {n} to {n}"),
+            Ok(vec!["1", "2", "13"])
+        );
+        assert_eq!(scan("x=-4, y=2", "x={i}, y={i}"), Ok(vec!["-4", "2"]));
+        assert_eq!(scan("AA: 5", "{w}: {n}"), Ok(vec!["AA", "5"]));
+        assert_eq!(
+            scan("move x from 2", "move {n} from {n}"),
+            Err(String::from("expected a number, found `x from 2`"))
+        );
+        assert_eq!(
+            scan("x=-", "x={i}"),
+            Err(String::from("expected a number, found `-`"))
+        );
+        assert_eq!(
+            scan("noop now", "noop"),
+            Err(String::from("unexpected ` now` at the end"))
+        );
+    }
+
+    #[test]
+    fn numbers_lines_across_blocks() {
+        let input = "1\n2\n\n3\n\nx\n";
+        let blocks = blocks(input);
+        assert_eq!(blocks, vec![(1, "1\n2"), (4, "3"), (6, "x\n")]);
+        let problems: Vec<Problem> = blocks
+            .into_iter()
+            .flat_map(|(first, block)| {
+                lines(first, block, |line| {
+                    line.parse::<u32>().map(|_| ()).map_err(|e| e.to_string())
+                })
+            })
+            .collect();
+        assert_eq!(
+            problems,
+            vec![Problem::new(6, "invalid digit found in string")]
+        );
+    }
+
+    #[test]
+    fn grids_are_rectangles() {
+        assert_eq!(grid(1, "#.#\n...\n", "#."), vec![]);
+        assert_eq!(
+            grid(1, "#.#\n..\n#x#\n", "#."),
+            vec![
+                Problem::new(2, "row is 2 wide, but the first row is 3 wide"),
+                Problem::new(3, "unexpected 'x', expected one of `#.`"),
+            ]
+        );
+        assert_eq!(
+            grid(1, "", "#."),
+            vec![Problem::new(1, "expected some lines, found none")]
+        );
+    }
+
+    #[test]
+    fn identifies_days_by_their_format() {
+        let candidates = identify(&YEAR, "R 4\nU 4\nL 3\n12\n");
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].day, 2);
+        assert_eq!(candidates[0].accepted, 0.75);
+        assert_eq!(candidates[0].confidence, 1.0);
+
+        let candidates = identify(&YEAR, "123\n456\nU 4\n");
+        let days: Vec<u8> = candidates.iter().map(|candidate| candidate.day).collect();
+        assert_eq!(days, [1, 3, 2]);
+        assert_eq!(candidates[0].confidence, candidates[1].confidence);
+        assert!(candidates[2].confidence < 0.01);
+    }
+}
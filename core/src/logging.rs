@@ -0,0 +1,52 @@
+use std::io::{stderr, IsTerminal};
+
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::{fmt::format::FmtSpan, EnvFilter};
+
+/// Sends diagnostics to stderr, so that stdout only has the answers.
+///
+/// `RUST_LOG` takes precedence when set. Otherwise `verbosity` counts `-v` flags, with each `-q`
+/// taking one away: warnings by default, span timings from `-v`, and events inside the solvers
+/// from `-vv`.
+pub fn init(verbosity: i8) {
+    let level = level(verbosity);
+    let filter = EnvFilter::builder()
+        .with_default_directive(level.into())
+        .from_env_lossy();
+    let spans = if level >= LevelFilter::INFO {
+        FmtSpan::CLOSE
+    } else {
+        FmtSpan::NONE
+    };
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_span_events(spans)
+        .with_writer(stderr)
+        .with_ansi(stderr().is_terminal())
+        .try_init();
+}
+
+fn level(verbosity: i8) -> LevelFilter {
+    match verbosity {
+        i8::MIN..=-2 => LevelFilter::OFF,
+        -1 => LevelFilter::ERROR,
+        0 => LevelFilter::WARN,
+        1 => LevelFilter::INFO,
+        2 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    }
+}
+
+#[cfg(test)]
+mod logging_tests {
+    use super::*;
+
+    #[test]
+    fn flags_move_the_level() {
+        assert_eq!(level(0), LevelFilter::WARN);
+        assert_eq!(level(-1), LevelFilter::ERROR);
+        assert_eq!(level(-5), LevelFilter::OFF);
+        assert_eq!(level(2), LevelFilter::DEBUG);
+        assert_eq!(level(7), LevelFilter::TRACE);
+    }
+}
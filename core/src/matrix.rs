@@ -0,0 +1,366 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    fs, io,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+use serde_json::Value;
+
+use crate::{
+    panic_message, solve_file,
+    theme::{Role, Theme},
+    DayRun, QuietPanics, Year,
+};
+
+/// Where a set keeps the answers its inputs should solve to, as `{"1": ["part 1", "part 2"]}`.
+pub const ANSWERS_FILE: &str = "answers.json";
+
+/// Longest answer shown in a cell before it is cut short.
+const ANSWER_WIDTH: usize = 16;
+
+/// Inputs for some of the days, such as one account's, with the answers they are known to have.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputSet {
+    pub name: String,
+    pub dir: PathBuf,
+    pub expected: BTreeMap<u8, [String; 2]>,
+}
+
+impl InputSet {
+    pub fn input_path(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day_{day}.txt"))
+    }
+}
+
+/// The year's inputs directory, and each directory inside it holding inputs, as input sets.
+///
+/// The inputs directory itself is named `main`, and also takes the answers in the bundle.
+pub fn find_sets(year: &Year) -> io::Result<Vec<InputSet>> {
+    let inputs = Path::new(year.inputs);
+    let mut sets = vec![read_set(String::from("main"), inputs)?];
+    for day in year.days() {
+        if let Some(expected) = year.expected_answers(day) {
+            sets[0].expected.entry(day).or_insert(expected);
+        }
+    }
+    let mut dirs: Vec<PathBuf> = fs::read_dir(inputs)?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+    for dir in dirs {
+        let name = dir.file_name().unwrap().to_string_lossy().into_owned();
+        let set = read_set(name, &dir)?;
+        if year.days().iter().any(|&day| set.input_path(day).exists()) {
+            sets.push(set);
+        }
+    }
+    Ok(sets)
+}
+
+fn read_set(name: String, dir: &Path) -> io::Result<InputSet> {
+    let mut expected = BTreeMap::new();
+    let path = dir.join(ANSWERS_FILE);
+    if path.exists() {
+        let invalid = || {
+            let message = format!("{} should map days to pairs of answers", path.display());
+            io::Error::new(io::ErrorKind::InvalidData, message)
+        };
+        let answers: Value = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        for (day, answers) in answers.as_object().ok_or_else(invalid)? {
+            let day = day.parse().map_err(|_| invalid())?;
+            let answer = |part: usize| answers[part].as_str().map(String::from);
+            let (Some(part_1), Some(part_2)) = (answer(0), answer(1)) else {
+                return Err(invalid());
+            };
+            expected.insert(day, [part_1, part_2]);
+        }
+    }
+    Ok(InputSet {
+        name,
+        dir: dir.to_owned(),
+        expected,
+    })
+}
+
+/// How one day went on one input set.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Cell {
+    /// The set has no input for the day.
+    Missing,
+    Failed(String),
+    Solved {
+        run: DayRun,
+        /// The answers the set expects, if it knows them.
+        expected: Option<[String; 2]>,
+    },
+}
+
+impl Cell {
+    /// Whether the part's answer is the expected one, if that is known.
+    pub fn is_correct(&self, part: usize) -> Option<bool> {
+        match self {
+            Cell::Solved {
+                run,
+                expected: Some(expected),
+            } => Some(expected[part].trim_end() == run.answers[part].trim_end()),
+            _ => None,
+        }
+    }
+
+    fn is_wrong(&self) -> bool {
+        match self {
+            Cell::Missing => false,
+            Cell::Failed(_) => true,
+            Cell::Solved { .. } => (0..2).any(|part| self.is_correct(part) == Some(false)),
+        }
+    }
+}
+
+/// Every day solved against every input set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix {
+    pub sets: Vec<String>,
+    pub days: Vec<u8>,
+    /// Cells by day, then by set.
+    pub cells: Vec<Vec<Cell>>,
+}
+
+impl Matrix {
+    /// Whether any cell failed or gave an answer other than the expected one.
+    pub fn has_failures(&self) -> bool {
+        self.cells.iter().flatten().any(Cell::is_wrong)
+    }
+}
+
+/// Solves the days against each set, carrying on past days that fail or panic.
+pub fn run(year: &Year, sets: &[InputSet], days: &[u8]) -> Matrix {
+    // panics are reported in their cells, not as they happen
+    let _quiet = QuietPanics::new();
+    let cells = days
+        .iter()
+        .map(|&day| sets.iter().map(|set| solve_cell(year, set, day)).collect())
+        .collect();
+    Matrix {
+        sets: sets.iter().map(|set| set.name.clone()).collect(),
+        days: days.to_vec(),
+        cells,
+    }
+}
+
+fn solve_cell(year: &Year, set: &InputSet, day: u8) -> Cell {
+    let path = set.input_path(day);
+    if !path.exists() {
+        return Cell::Missing;
+    }
+    match panic::catch_unwind(AssertUnwindSafe(|| solve_file(year, day, &path))) {
+        Ok(Ok(run)) => Cell::Solved {
+            run,
+            expected: set.expected.get(&day).cloned(),
+        },
+        Ok(Err(error)) => Cell::Failed(format!("unreadable: {error}")),
+        Err(payload) => Cell::Failed(format!("panicked: {}", panic_message(&payload))),
+    }
+}
+
+/// The matrix as a table with a row per day and a column per set, followed by what went wrong.
+pub fn render(theme: &Theme, matrix: &Matrix) -> String {
+    let texts: Vec<Vec<(Role, String)>> = matrix
+        .cells
+        .iter()
+        .map(|row| row.iter().map(cell_text).collect())
+        .collect();
+    let widths: Vec<usize> = (0..matrix.sets.len())
+        .map(|set| {
+            let cells = texts.iter().map(|row| row[set].1.chars().count());
+            cells.chain([matrix.sets[set].len()]).max().unwrap()
+        })
+        .collect();
+
+    let mut out = String::new();
+    let mut header = String::from("day");
+    for (name, width) in matrix.sets.iter().zip(&widths) {
+        write!(header, "  {name:<width$}").unwrap();
+    }
+    writeln!(out, "{}", theme.paint(Role::Header, header.trim_end())).unwrap();
+    for (day, row) in matrix.days.iter().zip(&texts) {
+        write!(out, "{}", theme.paint(Role::Label, format!("{day:>3}"))).unwrap();
+        for ((role, text), width) in row.iter().zip(&widths) {
+            write!(out, "  {}", theme.paint(*role, format!("{text:<width$}"))).unwrap();
+        }
+        out.truncate(out.trim_end().len());
+        out.push('\n');
+    }
+
+    for (day, row) in matrix.days.iter().zip(&matrix.cells) {
+        for (set, cell) in matrix.sets.iter().zip(row) {
+            let problem = match cell {
+                Cell::Failed(error) => error.clone(),
+                Cell::Solved {
+                    run,
+                    expected: Some(expected),
+                } if cell.is_wrong() => (0..2)
+                    .filter(|&part| cell.is_correct(part) == Some(false))
+                    .map(|part| {
+                        let (answer, expected) = (&run.answers[part], &expected[part]);
+                        format!(
+                            "part {} answered {}, expected {}",
+                            part + 1,
+                            first_line(answer),
+                            first_line(expected)
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("; "),
+                _ => continue,
+            };
+            let line = format!("day {day} on {set}: {problem}");
+            writeln!(out, "{}", theme.paint(Role::Error, line)).unwrap();
+        }
+    }
+    out
+}
+
+fn cell_text(cell: &Cell) -> (Role, String) {
+    match cell {
+        Cell::Missing => (Role::Separator, String::from("-")),
+        Cell::Failed(_) => (Role::Error, String::from("FAILED")),
+        Cell::Solved { run, .. } => {
+            let answers = [0, 1].map(|part| {
+                let mark = match cell.is_correct(part) {
+                    Some(true) => "✓",
+                    Some(false) => "✗",
+                    None => "",
+                };
+                format!("{}{mark}", first_line(&run.answers[part]))
+            });
+            let text = format!("{} {} {:.1?}", answers[0], answers[1], run.timings.total());
+            let role = if cell.is_wrong() {
+                Role::Error
+            } else {
+                Role::Answer
+            };
+            (role, text)
+        }
+    }
+}
+
+/// The answer's first line, cut short to fit in a cell.
+fn first_line(answer: &str) -> String {
+    let line = answer.trim().lines().next().unwrap_or_default();
+    if answer.trim().contains('\n') || line.chars().count() > ANSWER_WIDTH {
+        let short: String = line.chars().take(ANSWER_WIDTH - 1).collect();
+        format!("{short}…")
+    } else {
+        line.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod matrix_tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{Day, Timings};
+
+    struct Echo;
+
+    impl Day for Echo {
+        fn part_1(&self, input: &str) -> String {
+            input.trim().to_owned()
+        }
+
+        fn part_2(&self, input: &str) -> String {
+            if input.contains("panic") {
+                panic!("told to");
+            }
+            input.len().to_string()
+        }
+    }
+
+    fn year(dir: &'static str) -> Year {
+        Year {
+            year: 2022,
+            get: |day| (day == 1 || day == 2).then_some(&Echo as &dyn Day),
+            inputs: dir,
+            version: "0.1.0",
+            cache: Box::leak(format!("{dir}/cache").into_boxed_str()),
+            bundle: "",
+            history: "",
+            embedded: &[],
+        }
+    }
+
+    #[test]
+    fn runs_every_day_on_every_set() {
+        let dir = std::env::temp_dir().join(format!("aoc_matrix_{}", std::process::id()));
+        let other = dir.join("alice");
+        fs::create_dir_all(&other).unwrap();
+        fs::write(dir.join("day_1.txt"), "hi\n").unwrap();
+        fs::write(dir.join("day_2.txt"), "panic\n").unwrap();
+        fs::write(other.join("day_1.txt"), "hello\n").unwrap();
+        fs::write(other.join(ANSWERS_FILE), r#"{"1": ["hello", "5"]}"#).unwrap();
+        fs::create_dir_all(dir.join("empty")).unwrap();
+        let year = year(Box::leak(dir.to_str().unwrap().into()));
+
+        let sets = find_sets(&year).unwrap();
+        let names: Vec<&str> = sets.iter().map(|set| set.name.as_str()).collect();
+        assert_eq!(names, ["main", "alice"]);
+
+        let matrix = run(&year, &sets, &[1, 2]);
+        fs::remove_dir_all(dir).unwrap();
+        assert_eq!(matrix.cells[0][0].is_correct(0), None);
+        assert_eq!(matrix.cells[0][1].is_correct(0), Some(true));
+        assert_eq!(matrix.cells[0][1].is_correct(1), Some(false));
+        assert_eq!(
+            matrix.cells[1][0],
+            Cell::Failed(String::from("panicked: told to"))
+        );
+        assert_eq!(matrix.cells[1][1], Cell::Missing);
+        assert!(matrix.has_failures());
+    }
+
+    #[test]
+    fn renders_a_table() {
+        let run = |answers: [&str; 2]| DayRun {
+            day: 1,
+            answers: answers.map(String::from),
+            cached: [false; 2],
+            timings: Timings {
+                input: Duration::ZERO,
+                parts: [Duration::from_millis(1); 2],
+            },
+            allocations: None,
+        };
+        let matrix = Matrix {
+            sets: vec![String::from("main"), String::from("alice")],
+            days: vec![1, 10],
+            cells: vec![
+                vec![
+                    Cell::Solved {
+                        run: run(["24000", "45000"]),
+                        expected: Some([String::from("24000"), String::from("45001")]),
+                    },
+                    Cell::Missing,
+                ],
+                vec![
+                    Cell::Failed(String::from("panicked: oops")),
+                    Cell::Solved {
+                        run: run(["13140", "##..##\n#..#\n"]),
+                        expected: None,
+                    },
+                ],
+            ],
+        };
+        assert_eq!(
+            render(&Theme::MONOCHROME, &matrix),
+            "day  main                 alice\n\
+             \x20 1  24000✓ 45000✗ 2.0ms  -\n\
+             \x2010  FAILED               13140 ##..##… 2.0ms\n\
+             day 1 on main: part 2 answered 45000, expected 45001\n\
+             day 10 on main: panicked: oops\n"
+        );
+    }
+}
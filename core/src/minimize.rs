@@ -0,0 +1,388 @@
+use std::{
+    fmt::Display,
+    panic::{self, AssertUnwindSafe},
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
+
+use tracing::debug;
+
+use crate::{panic_message, solve_part, stream, Day, QuietPanics};
+
+/// How a solver goes wrong on an input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    Panic {
+        part: u8,
+        message: String,
+    },
+    /// Streaming the input gave a different answer from solving it whole.
+    Disagreement {
+        part: u8,
+        whole: String,
+        streamed: String,
+    },
+}
+
+impl Failure {
+    /// Whether `other` is the same failure, ignoring any numbers in it, which tend to change as
+    /// the input shrinks.
+    pub fn matches(&self, other: &Failure) -> bool {
+        match (self, other) {
+            (
+                Failure::Panic { part, message },
+                Failure::Panic {
+                    part: other_part,
+                    message: other_message,
+                },
+            ) => part == other_part && without_digits(message) == without_digits(other_message),
+            (Failure::Disagreement { part, .. }, Failure::Disagreement { part: other, .. }) => {
+                part == other
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Panic { part, message } => write!(f, "part {part} panicked: {message}"),
+            Failure::Disagreement {
+                part,
+                whole,
+                streamed,
+            } => write!(
+                f,
+                "part {part} answered {} whole, but {} streamed",
+                whole.trim(),
+                streamed.trim()
+            ),
+        }
+    }
+}
+
+fn without_digits(message: &str) -> String {
+    message.chars().filter(|c| !c.is_ascii_digit()).collect()
+}
+
+/// How running a solver on an input went.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Checked {
+    Passed,
+    Failed(Failure),
+    /// The solver was still running after the limit, and is left to finish in the background.
+    TimedOut,
+}
+
+/// How the solver fares on the input, giving up on it after `limit`.
+pub fn check(solver: &'static dyn Day, input: &str, limit: Duration) -> Checked {
+    let (sender, receiver) = mpsc::channel();
+    let input = input.to_owned();
+    thread::spawn(move || {
+        let _ = sender.send(failure(solver, &input));
+    });
+    match receiver.recv_timeout(limit) {
+        Ok(Some(failure)) => Checked::Failed(failure),
+        Ok(None) => Checked::Passed,
+        Err(_) => Checked::TimedOut,
+    }
+}
+
+fn failure(solver: &dyn Day, input: &str) -> Option<Failure> {
+    for part in 1..=2 {
+        let panicked = |payload| Failure::Panic {
+            part,
+            message: panic_message(&payload),
+        };
+        let whole = panic::catch_unwind(AssertUnwindSafe(|| solve_part(solver, part, input)));
+        let whole = match whole {
+            Ok(solution) => solution.unwrap().answer,
+            Err(payload) => return Some(panicked(payload)),
+        };
+        let Some(stream) = solver.streaming() else {
+            continue;
+        };
+        let streamed = panic::catch_unwind(AssertUnwindSafe(|| {
+            stream::solve(stream, part, &mut input.as_bytes())
+        }));
+        match streamed {
+            Ok(Ok(streamed)) if streamed.answer != whole => {
+                return Some(Failure::Disagreement {
+                    part,
+                    whole,
+                    streamed: streamed.answer,
+                })
+            }
+            Err(payload) => return Some(panicked(payload)),
+            // input the stream rejects is not what this is looking for
+            Ok(_) => {}
+        }
+    }
+    None
+}
+
+/// The smallest input found, and how many candidates were tried on the way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Minimized {
+    pub input: String,
+    pub tries: usize,
+    /// Whether the search ended early, when a candidate could no longer be told apart.
+    pub stopped: bool,
+}
+
+/// Shrinks `input` as far as it can while the solver keeps failing the way it does on `input`,
+/// and the input stays at least as well formed as it was.
+///
+/// Returns `None` when the solver does not fail on `input` within `limit` to begin with. The search
+/// stops at the first candidate that runs past `limit`, so that hanging solvers do not pile up.
+pub fn minimize_day(
+    solver: &'static dyn Day,
+    input: &str,
+    limit: Duration,
+) -> Option<(Failure, Minimized)> {
+    // panics are what is being looked for, not news
+    let _quiet = QuietPanics::new();
+    let Checked::Failed(original) = check(solver, input, limit) else {
+        return None;
+    };
+    let problems = |input: &str| solver.lint(input).map_or(0, |problems| problems.len());
+    let allowed = problems(input);
+    let minimized = minimize(
+        input,
+        &|candidate| problems(candidate) <= allowed,
+        &mut |candidate| match check(solver, candidate, limit) {
+            Checked::Passed => Some(false),
+            Checked::Failed(failure) => Some(original.matches(&failure)),
+            Checked::TimedOut => None,
+        },
+    );
+    Some((original, minimized))
+}
+
+/// Shrinks `input` by removing blocks, then lines, then tokens, and by making numbers smaller,
+/// keeping each change for which the result is `well_formed` and still `fails`, until none helps.
+///
+/// `fails` answers `None` when it cannot tell, which ends the search with what it has found.
+pub fn minimize(
+    input: &str,
+    well_formed: &dyn Fn(&str) -> bool,
+    fails: &mut dyn FnMut(&str) -> Option<bool>,
+) -> Minimized {
+    let mut search = Search {
+        well_formed,
+        fails,
+        tries: 0,
+        stopped: false,
+    };
+    let ending = if input.ends_with('\n') { "\n" } else { "" };
+    let mut current = input.to_owned();
+    loop {
+        let before = current.clone();
+        let blocks = current.split("\n\n").map(String::from).collect();
+        current = search.remove(blocks, &|blocks| blocks.join("\n\n"));
+        let lines = current.lines().map(String::from).collect();
+        current = search.remove(lines, &|lines| lines.join("\n") + ending);
+        // the final line ending stays, as inputs end with one
+        let body = tokens(current.strip_suffix(ending).unwrap_or(&current));
+        current = search.remove(body, &|tokens| tokens.concat() + ending);
+        current = search.lower_numbers(tokens(&current));
+        debug!(bytes = current.len(), tries = search.tries, "shrank");
+        if current == before {
+            return Minimized {
+                input: current,
+                tries: search.tries,
+                stopped: search.stopped,
+            };
+        }
+    }
+}
+
+struct Search<'a> {
+    well_formed: &'a dyn Fn(&str) -> bool,
+    fails: &'a mut dyn FnMut(&str) -> Option<bool>,
+    tries: usize,
+    stopped: bool,
+}
+
+impl Search<'_> {
+    fn accepts(&mut self, candidate: &str) -> bool {
+        if self.stopped || !(self.well_formed)(candidate) {
+            return false;
+        }
+        self.tries += 1;
+        let fails = (self.fails)(candidate);
+        self.stopped = fails.is_none();
+        fails == Some(true)
+    }
+
+    /// Removes runs of units, halving the length of the runs tried until it is one.
+    fn remove(&mut self, mut units: Vec<String>, join: &dyn Fn(&[String]) -> String) -> String {
+        let mut run = units.len() / 2;
+        while run > 0 {
+            let mut start = 0;
+            while start < units.len() {
+                let end = (start + run).min(units.len());
+                let candidate: Vec<String> = units[..start]
+                    .iter()
+                    .chain(&units[end..])
+                    .cloned()
+                    .collect();
+                if self.accepts(&join(&candidate)) {
+                    units = candidate;
+                } else {
+                    start += run;
+                }
+            }
+            run /= 2;
+        }
+        join(&units)
+    }
+
+    /// Brings each number down to 0, 1 or half of what it was, for as long as one of those helps.
+    fn lower_numbers(&mut self, mut tokens: Vec<String>) -> String {
+        for index in 0..tokens.len() {
+            while let Ok(number) = tokens[index].parse::<u64>() {
+                let original = std::mem::take(&mut tokens[index]);
+                let lower = [0, 1, number / 2]
+                    .into_iter()
+                    .filter(|&lower| lower < number);
+                let accepted = lower.into_iter().find(|lower| {
+                    tokens[index] = lower.to_string();
+                    self.accepts(&tokens.concat())
+                });
+                if accepted.is_none() {
+                    tokens[index] = original;
+                    break;
+                }
+            }
+        }
+        tokens.concat()
+    }
+}
+
+/// The input split into runs of letters and digits, and single other characters.
+fn tokens(input: &str) -> Vec<String> {
+    let mut tokens: Vec<String> = Vec::new();
+    let mut previous_word = false;
+    for c in input.chars() {
+        let word = c.is_ascii_alphanumeric();
+        match tokens.last_mut() {
+            Some(token) if word && previous_word => token.push(c),
+            _ => tokens.push(c.to_string()),
+        }
+        previous_word = word;
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod minimize_tests {
+    use super::*;
+    use crate::lint::{self, Problem};
+
+    struct Pairs;
+
+    impl Day for Pairs {
+        fn part_1(&self, input: &str) -> String {
+            input.lines().count().to_string()
+        }
+
+        fn part_2(&self, input: &str) -> String {
+            for number in input.split_whitespace() {
+                let number: u32 = number.parse().unwrap();
+                if number >= 7 {
+                    panic!("too big: {number}");
+                }
+            }
+            String::from("fine")
+        }
+
+        fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+            Some(lint::lines(1, input, |line| {
+                lint::scan(line, "{n} {n}").map(drop)
+            }))
+        }
+    }
+
+    struct Sleepy;
+
+    impl Day for Sleepy {
+        fn part_1(&self, input: &str) -> String {
+            if input == "1 9\n" {
+                panic!("the original");
+            }
+            thread::sleep(Duration::from_millis(300));
+            String::new()
+        }
+
+        fn part_2(&self, _input: &str) -> String {
+            String::new()
+        }
+    }
+
+    #[test]
+    fn splits_tokens() {
+        assert_eq!(
+            tokens("move 12 from\n[Z]"),
+            ["move", " ", "12", " ", "from", "\n", "[", "Z", "]"]
+        );
+    }
+
+    #[test]
+    fn keeps_what_the_failure_needs() {
+        let input: String = (1..=100).map(|line| format!("{line}\n")).collect();
+        let minimized = minimize(
+            &input,
+            &|candidate| !candidate.is_empty(),
+            &mut |candidate| {
+                let lines: Vec<&str> = candidate.lines().collect();
+                Some(lines.contains(&"42") && lines.contains(&"57"))
+            },
+        );
+        assert_eq!(minimized.input, "42\n57\n");
+        assert!(minimized.tries < 200);
+        assert!(!minimized.stopped);
+    }
+
+    #[test]
+    fn stops_when_a_candidate_cannot_be_told_apart() {
+        let input: String = (1..=100).map(|line| format!("{line}\n")).collect();
+        let mut tries = 0;
+        let minimized = minimize(&input, &|_| true, &mut |_| {
+            tries += 1;
+            (tries < 2).then_some(true)
+        });
+        assert!(minimized.stopped);
+        assert_eq!(minimized.tries, 2);
+        assert_eq!(minimized.input.lines().count(), 50);
+    }
+
+    #[test]
+    fn shrinks_inputs_that_make_a_day_panic() {
+        let input = "3 4\n2 100\n5 8\n1 1\n";
+        let (failure, minimized) = minimize_day(&Pairs, input, Duration::from_secs(5)).unwrap();
+        assert_eq!(
+            failure,
+            Failure::Panic {
+                part: 2,
+                message: String::from("too big: 100")
+            }
+        );
+        assert_eq!(minimized.input, "0 8\n");
+        assert!(minimize_day(&Pairs, "1 2\n", Duration::from_secs(5)).is_none());
+    }
+
+    #[test]
+    fn stops_at_the_first_candidate_that_hangs() {
+        // fails on the input, and hangs on anything smaller
+        let input = "1 9\n";
+        let minimized = minimize_day(&Sleepy, input, Duration::from_millis(100))
+            .unwrap()
+            .1;
+        assert!(minimized.stopped);
+        assert_eq!(minimized.tries, 1);
+        assert_eq!(minimized.input, input);
+    }
+}
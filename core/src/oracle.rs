@@ -0,0 +1,365 @@
+use std::{
+    fmt::Write as _,
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    time::Duration,
+};
+
+use crate::{
+    generate::Rng,
+    isolate::{self, drain, wait, Limits},
+    theme::{Role, Theme},
+    Year,
+};
+
+/// A reference solver, run as a shell command with `{day}`, `{part}` and `{input}` replaced.
+///
+/// Without `{input}` in the template, the input is given on standard input instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Oracle {
+    pub template: String,
+}
+
+impl Oracle {
+    pub fn new(template: impl Into<String>) -> Self {
+        Self {
+            template: template.into(),
+        }
+    }
+
+    pub fn command(&self, day: u8, part: u8, input: &Path) -> String {
+        self.template
+            .replace("{day}", &day.to_string())
+            .replace("{part}", &part.to_string())
+            .replace("{input}", &quote(&input.to_string_lossy()))
+    }
+
+    /// What the reference answers for the input at `input`, or why it did not.
+    pub fn answer(
+        &self,
+        day: u8,
+        part: u8,
+        input: &Path,
+        limit: Duration,
+    ) -> Result<String, String> {
+        let mut command = Command::new("sh");
+        command
+            .arg("-c")
+            .arg(self.command(day, part, input))
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        if self.template.contains("{input}") {
+            command.stdin(Stdio::null());
+        } else {
+            let file = File::open(input).map_err(|error| error.to_string())?;
+            command.stdin(file);
+        }
+        let mut child = command
+            .spawn()
+            .map_err(|error| format!("could not start: {error}"))?;
+        let stdout = drain(child.stdout.take());
+        let stderr = drain(child.stderr.take());
+        let waited = wait(&mut child, limit);
+        let (stdout, stderr) = (stdout.join().unwrap(), stderr.join().unwrap());
+        match waited {
+            Err(error) => Err(format!("could not wait for it: {error}")),
+            Ok(None) => Err(format!("no answer within {limit:?}")),
+            Ok(Some(status)) if status.success() => Ok(stdout.trim().to_owned()),
+            Ok(Some(status)) => match stderr.trim().lines().last() {
+                Some(line) => Err(format!("{status}: {line}")),
+                None => Err(status.to_string()),
+            },
+        }
+    }
+}
+
+/// Quotes `text` for `sh`.
+fn quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+/// An input and part that the day and the reference answered differently.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub day: u8,
+    pub part: u8,
+    /// The input they disagree on, kept on disk to reproduce it.
+    pub input: PathBuf,
+    /// The day's answer, or why there is none.
+    pub ours: Result<String, String>,
+    pub theirs: Result<String, String>,
+}
+
+/// How the days compared with the reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparison {
+    /// Answers compared, one per input and part.
+    pub checked: usize,
+    pub mismatches: Vec<Mismatch>,
+}
+
+/// Answers each day's real input, and `generated` inputs made from seeds counting up from `seed`,
+/// with both the day and the reference, each limited to the day's budget. The day solves each in a
+/// child process of its own, so that one hanging or crashing is only a mismatch.
+///
+/// Inputs are written to `dir` when they are not already on disk, which for an input only in the
+/// bundle means in plain text. Those are only kept there if the answers to them differ.
+pub fn compare(
+    year: &Year,
+    oracle: &Oracle,
+    days: &[u8],
+    generated: u64,
+    seed: u64,
+    dir: &Path,
+) -> io::Result<Comparison> {
+    let isolated =
+        |day, part, input: &Path| isolate::run_part(year, day, part, input, &Limits::default());
+    compare_with(year, oracle, days, generated, seed, dir, &isolated)
+}
+
+/// Compares like [`compare`], with the day's answers coming from `ours`.
+fn compare_with(
+    year: &Year,
+    oracle: &Oracle,
+    days: &[u8],
+    generated: u64,
+    seed: u64,
+    dir: &Path,
+    ours: &dyn Fn(u8, u8, &Path) -> Result<String, String>,
+) -> io::Result<Comparison> {
+    fs::create_dir_all(dir)?;
+    let mut comparison = Comparison {
+        checked: 0,
+        mismatches: Vec::new(),
+    };
+    for &day in days {
+        let Some(solver) = year.get(day) else {
+            continue;
+        };
+        // each input, and whether it was written here only to be compared
+        let mut inputs = Vec::new();
+        let real = year.input_path(day);
+        if real.exists() {
+            inputs.push((real, false));
+        } else if let Ok(input) = year.read_input(day) {
+            let path = dir.join(format!("day_{day}.txt"));
+            fs::write(&path, input)?;
+            inputs.push((path, true));
+        }
+        // seeds past the largest there is are not tried
+        for seed in (0..generated).map_while(|offset| seed.checked_add(offset)) {
+            let Some(input) = solver.generate(&mut Rng::new(seed)) else {
+                break;
+            };
+            let path = dir.join(format!("day_{day}_seed_{seed}.txt"));
+            fs::write(&path, input)?;
+            inputs.push((path, true));
+        }
+
+        for (path, written) in inputs {
+            let mut agreed = true;
+            for part in 1..=2 {
+                let ours = ours(day, part, &path).map(|answer| answer.trim().to_owned());
+                let theirs = oracle.answer(day, part, &path, solver.budget());
+                comparison.checked += 1;
+                if ours.is_err() || ours != theirs {
+                    agreed = false;
+                    comparison.mismatches.push(Mismatch {
+                        day,
+                        part,
+                        input: path.clone(),
+                        ours,
+                        theirs,
+                    });
+                }
+            }
+            if written && agreed {
+                fs::remove_file(&path)?;
+            }
+        }
+    }
+    Ok(comparison)
+}
+
+/// How many answers were compared, then each mismatch with the input that caused it.
+pub fn render(theme: &Theme, comparison: &Comparison) -> String {
+    let mut out = String::new();
+    let summary = format!(
+        "compared {} answers, {} differed",
+        comparison.checked,
+        comparison.mismatches.len()
+    );
+    writeln!(out, "{}", theme.paint(Role::Header, summary)).unwrap();
+    for mismatch in &comparison.mismatches {
+        let answer = |answer: &Result<String, String>| match answer {
+            Ok(answer) if answer.contains('\n') => format!("\n{answer}\n"),
+            Ok(answer) => answer.clone(),
+            Err(error) => format!("none ({error})"),
+        };
+        let line = format!(
+            "day {} part {} on {}: ours {}, theirs {}",
+            mismatch.day,
+            mismatch.part,
+            mismatch.input.display(),
+            answer(&mismatch.ours),
+            answer(&mismatch.theirs)
+        );
+        writeln!(out, "{}", theme.paint(Role::Error, line.trim_end())).unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod oracle_tests {
+    use super::*;
+    use crate::{solve_part, Day};
+
+    struct Echo;
+
+    impl Day for Echo {
+        fn part_1(&self, input: &str) -> String {
+            input.trim().to_owned()
+        }
+
+        fn part_2(&self, input: &str) -> String {
+            input.len().to_string()
+        }
+
+        fn generate(&self, rng: &mut Rng) -> Option<String> {
+            Some(format!("{}\n", rng.range(0..=999)))
+        }
+    }
+
+    #[test]
+    fn fills_in_templates() {
+        let oracle = Oracle::new("ref-solver {day} {part} < {input}");
+        assert_eq!(
+            oracle.command(7, 2, Path::new("inputs/it's.txt")),
+            r"ref-solver 7 2 < 'inputs/it'\''s.txt'"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn reports_inputs_answered_differently() {
+        let dir = std::env::temp_dir().join(format!("aoc_oracle_{}", std::process::id()));
+        let output = dir.join("oracle");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day_1.txt"), "hello\n").unwrap();
+        let year = Year {
+            year: 2022,
+            get: |day| (day == 1).then_some(&Echo as &dyn Day),
+            inputs: Box::leak(dir.to_str().unwrap().into()),
+            version: "0.1.0",
+            cache: "",
+            bundle: "",
+            history: "",
+            embedded: &[],
+        };
+
+        // in this process, as a child would be a copy of the test runner
+        let ours = |day, part, input: &Path| {
+            let input = fs::read_to_string(input).unwrap();
+            Ok(solve_part(year.get(day).unwrap(), part, &input)
+                .unwrap()
+                .answer)
+        };
+        let compare = |oracle: &Oracle, days: &[u8]| {
+            compare_with(&year, oracle, days, 3, 10, &output, &ours).unwrap()
+        };
+
+        let agreeing = Oracle::new("if [ {part} = 1 ]; then cat {input}; else wc -c < {input}; fi");
+        let comparison = compare(&agreeing, &[1, 2]);
+        assert_eq!(comparison.checked, 8);
+        assert_eq!(comparison.mismatches, vec![]);
+        assert!(!output.join("day_1_seed_10.txt").exists());
+
+        // answers the first part to everything
+        let comparison = compare(&Oracle::new("cat"), &[1]);
+        let kept = output.join("day_1_seed_11.txt");
+        let input = fs::read_to_string(&kept).unwrap();
+        fs::remove_dir_all(dir).unwrap();
+        assert_eq!(comparison.mismatches.len(), 4);
+        assert_eq!(
+            comparison.mismatches[2],
+            Mismatch {
+                day: 1,
+                part: 2,
+                input: kept,
+                ours: Ok(input.len().to_string()),
+                theirs: Ok(input.trim().to_owned()),
+            }
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn removes_inputs_written_only_to_compare() {
+        let dir = std::env::temp_dir().join(format!("aoc_oracle_plain_{}", std::process::id()));
+        let year = Year {
+            year: 2022,
+            get: |day| (day == 1).then_some(&Echo as &dyn Day),
+            inputs: Box::leak(dir.join("inputs").to_str().unwrap().into()),
+            version: "0.1.0",
+            cache: "",
+            bundle: "",
+            history: "",
+            embedded: &[(1, "secret\n")],
+        };
+        let ours = |_, _, input: &Path| Ok(fs::read_to_string(input).unwrap());
+        let written = dir.join("day_1.txt");
+
+        let disagreeing = Oracle::new("echo public");
+        let comparison = compare_with(&year, &disagreeing, &[1], 0, 0, &dir, &ours);
+        assert_eq!(comparison.unwrap().mismatches.len(), 2);
+        assert!(written.exists());
+        let agreeing = Oracle::new("cat {input}");
+        let comparison = compare_with(&year, &agreeing, &[1], 0, 0, &dir, &ours);
+        let left = written.exists();
+        fs::remove_dir_all(dir).unwrap();
+        assert_eq!(comparison.unwrap().mismatches, vec![]);
+        assert!(!left);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stops_at_the_last_seed() {
+        let dir = std::env::temp_dir().join(format!("aoc_oracle_seeds_{}", std::process::id()));
+        let year = Year {
+            year: 2022,
+            get: |day| (day == 1).then_some(&Echo as &dyn Day),
+            inputs: Box::leak(dir.join("inputs").to_str().unwrap().into()),
+            version: "0.1.0",
+            cache: "",
+            bundle: "",
+            history: "",
+            embedded: &[],
+        };
+        let ours = |_, _, input: &Path| Ok(fs::read_to_string(input).unwrap());
+        let oracle = Oracle::new("cat {input}");
+        let comparison = compare_with(&year, &oracle, &[1], 5, u64::MAX - 1, &dir, &ours);
+        fs::remove_dir_all(dir).unwrap();
+        assert_eq!(comparison.unwrap().checked, 4);
+    }
+
+    #[test]
+    fn renders_mismatches() {
+        let comparison = Comparison {
+            checked: 4,
+            mismatches: vec![Mismatch {
+                day: 9,
+                part: 2,
+                input: PathBuf::from("oracle/day_9_seed_3.txt"),
+                ours: Ok(String::from("36")),
+                theirs: Err(String::from("exit status: 1: no such file")),
+            }],
+        };
+        assert_eq!(
+            render(&Theme::MONOCHROME, &comparison),
+            "compared 4 answers, 1 differed\n\
+             day 9 part 2 on oracle/day_9_seed_3.txt: ours 36, theirs none (exit status: 1: no such file)\n"
+        );
+    }
+}
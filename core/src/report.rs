@@ -0,0 +1,407 @@
+use std::{
+    fmt::Write,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{alloc::AllocStats, DayRun};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Markdown,
+    Html,
+}
+
+impl Format {
+    /// Guesses the format from a file extension, defaulting to Markdown.
+    pub fn from_extension(extension: Option<&str>) -> Self {
+        match extension {
+            Some("html" | "htm") => Self::Html,
+            _ => Self::Markdown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFormatError(String);
+
+impl std::fmt::Display for ParseFormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown report format `{}`, expected md or html", self.0)
+    }
+}
+
+impl std::error::Error for ParseFormatError {}
+
+impl FromStr for Format {
+    type Err = ParseFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "md" | "markdown" => Ok(Self::Markdown),
+            "html" => Ok(Self::Html),
+            _ => Err(ParseFormatError(s.to_owned())),
+        }
+    }
+}
+
+/// Where the answers were computed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Machine {
+    pub os: String,
+    pub cpu: String,
+    pub threads: usize,
+    pub rustc: String,
+    pub profile: String,
+}
+
+impl Machine {
+    pub fn current() -> Self {
+        let cpu = std::fs::read_to_string("/proc/cpuinfo")
+            .ok()
+            .and_then(|info| {
+                info.lines()
+                    .find(|line| line.starts_with("model name"))
+                    .and_then(|line| line.split_once(':'))
+                    .map(|(_, model)| model.trim().to_owned())
+            })
+            .unwrap_or_else(|| String::from("unknown"));
+        Self {
+            os: format!("{} ({})", std::env::consts::OS, env!("AOC_TARGET")),
+            cpu,
+            threads: std::thread::available_parallelism().map_or(1, usize::from),
+            rustc: env!("AOC_RUSTC_VERSION").to_owned(),
+            profile: env!("AOC_PROFILE").to_owned(),
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "os": self.os,
+            "cpu": self.cpu,
+            "threads": self.threads,
+            "rustc": self.rustc,
+            "profile": self.profile,
+        })
+    }
+
+    pub fn from_json(value: &serde_json::Value) -> Option<Self> {
+        let text = |key: &str| value[key].as_str().map(String::from);
+        Some(Self {
+            os: text("os")?,
+            cpu: text("cpu")?,
+            threads: value["threads"].as_u64()?.try_into().ok()?,
+            rustc: text("rustc")?,
+            profile: text("profile")?,
+        })
+    }
+
+    fn rows(&self) -> [(&str, String); 5] {
+        [
+            ("OS", self.os.clone()),
+            ("CPU", self.cpu.clone()),
+            ("Threads", self.threads.to_string()),
+            ("Toolchain", self.rustc.clone()),
+            ("Profile", self.profile.clone()),
+        ]
+    }
+}
+
+/// Answers and timings for a set of days, ready to be written out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub year: u16,
+    pub runs: Vec<DayRun>,
+    pub machine: Machine,
+    /// Seconds since the Unix epoch.
+    pub generated: u64,
+}
+
+impl Report {
+    pub fn new(year: u16, runs: Vec<DayRun>) -> Self {
+        Self {
+            year,
+            runs,
+            machine: Machine::current(),
+            generated: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_secs()),
+        }
+    }
+
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Markdown => self.to_markdown(),
+            Format::Html => self.to_html(),
+        }
+    }
+
+    fn total(&self) -> Duration {
+        self.runs.iter().map(|run| run.timings.total()).sum()
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "# Advent of Code {}\n", self.year).unwrap();
+        writeln!(out, "Generated {}.\n", timestamp(self.generated)).unwrap();
+
+        writeln!(out, "## Summary\n").unwrap();
+        writeln!(out, "| Day | Input | Part 1 | Part 2 | Total |").unwrap();
+        writeln!(out, "| --: | --: | --: | --: | --: |").unwrap();
+        for run in &self.runs {
+            let timings = &run.timings;
+            writeln!(
+                out,
+                "| {} | {} | {} | {} | {} |",
+                run.day,
+                duration(timings.input),
+                duration(timings.parts[0]),
+                duration(timings.parts[1]),
+                duration(timings.total())
+            )
+            .unwrap();
+        }
+        writeln!(out, "| **All** | | | | **{}** |\n", duration(self.total())).unwrap();
+
+        for run in &self.runs {
+            writeln!(out, "## Day {}\n", run.day).unwrap();
+            for (part, answer) in run.answers.iter().enumerate() {
+                write!(out, "**{}:** ", part_label(run, part)).unwrap();
+                if answer.contains('\n') {
+                    writeln!(out, "\n\n```text\n{}\n```\n", answer.trim_end()).unwrap();
+                } else {
+                    writeln!(out, "`{answer}`\n").unwrap();
+                }
+            }
+            if let Some(allocations) = &run.allocations {
+                for (part, stats) in allocations.iter().enumerate() {
+                    writeln!(out, "- Part {} allocated {}", part + 1, allocated(stats)).unwrap();
+                }
+                writeln!(out).unwrap();
+            }
+        }
+
+        writeln!(out, "## Machine\n").unwrap();
+        writeln!(out, "| | |\n| --- | --- |").unwrap();
+        for (name, value) in self.machine.rows() {
+            writeln!(out, "| {name} | {value} |").unwrap();
+        }
+        out
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut out = String::new();
+        out.push_str(concat!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n",
+            "<style>\n",
+            "body { font-family: sans-serif; max-width: 60rem; margin: 2rem auto; ",
+            "color: #303446; }\n",
+            "table { border-collapse: collapse; }\n",
+            "th, td { border: 1px solid #c6d0f5; padding: 0.25rem 0.75rem; }\n",
+            "td.number { text-align: right; font-variant-numeric: tabular-nums; }\n",
+            "pre, code { background: #eff1f5; font-family: monospace; }\n",
+            "pre { padding: 0.5rem; line-height: 1; }\n",
+            "</style>\n",
+        ));
+        writeln!(
+            out,
+            "<title>Advent of Code {0}</title>\n</head>\n<body>\n<h1>Advent of Code {0}</h1>",
+            self.year
+        )
+        .unwrap();
+        writeln!(out, "<p>Generated {}.</p>", timestamp(self.generated)).unwrap();
+
+        out.push_str("<h2>Summary</h2>\n<table>\n");
+        out.push_str(
+            "<tr><th>Day</th><th>Input</th><th>Part 1</th><th>Part 2</th><th>Total</th></tr>\n",
+        );
+        for run in &self.runs {
+            let timings = &run.timings;
+            out.push_str("<tr>");
+            write!(out, "<td class=\"number\">{}</td>", run.day).unwrap();
+            for time in [
+                timings.input,
+                timings.parts[0],
+                timings.parts[1],
+                timings.total(),
+            ] {
+                write!(out, "<td class=\"number\">{}</td>", escape(&duration(time))).unwrap();
+            }
+            out.push_str("</tr>\n");
+        }
+        writeln!(
+            out,
+            "<tr><th>All</th><td></td><td></td><td></td><th class=\"number\">{}</th></tr>\n</table>",
+            escape(&duration(self.total()))
+        )
+        .unwrap();
+
+        for run in &self.runs {
+            writeln!(out, "<h2>Day {}</h2>", run.day).unwrap();
+            for (part, answer) in run.answers.iter().enumerate() {
+                if answer.contains('\n') {
+                    writeln!(
+                        out,
+                        "<p><strong>{}:</strong></p>\n<pre>{}</pre>",
+                        part_label(run, part),
+                        escape(answer.trim_end())
+                    )
+                    .unwrap();
+                } else {
+                    writeln!(
+                        out,
+                        "<p><strong>{}:</strong> <code>{}</code></p>",
+                        part_label(run, part),
+                        escape(answer)
+                    )
+                    .unwrap();
+                }
+            }
+            if let Some(allocations) = &run.allocations {
+                out.push_str("<ul>\n");
+                for (part, stats) in allocations.iter().enumerate() {
+                    writeln!(
+                        out,
+                        "<li>Part {} allocated {}</li>",
+                        part + 1,
+                        allocated(stats)
+                    )
+                    .unwrap();
+                }
+                out.push_str("</ul>\n");
+            }
+        }
+
+        out.push_str("<h2>Machine</h2>\n<table>\n");
+        for (name, value) in self.machine.rows() {
+            writeln!(out, "<tr><th>{name}</th><td>{}</td></tr>", escape(&value)).unwrap();
+        }
+        out.push_str("</table>\n</body>\n</html>\n");
+        out
+    }
+}
+
+/// Cached answers are called out, since their timings are only the cache lookup.
+fn part_label(run: &DayRun, part: usize) -> String {
+    if run.cached[part] {
+        format!("Part {} (cached)", part + 1)
+    } else {
+        format!("Part {}", part + 1)
+    }
+}
+
+fn duration(duration: Duration) -> String {
+    format!("{duration:.2?}")
+}
+
+fn allocated(stats: &AllocStats) -> String {
+    format!("{} times, {} bytes", stats.allocations, stats.bytes)
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Formats seconds since the Unix epoch as an ISO 8601 UTC timestamp.
+pub(crate) fn timestamp(seconds: u64) -> String {
+    let days = (seconds / 86_400) as i64;
+    let time = seconds % 86_400;
+    // civil-from-days, from Howard Hinnant's date algorithms
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        time / 3_600,
+        time / 60 % 60,
+        time % 60
+    )
+}
+
+#[cfg(test)]
+mod report_tests {
+    use super::*;
+    use crate::Timings;
+
+    fn report() -> Report {
+        Report {
+            year: 2022,
+            runs: vec![DayRun {
+                day: 10,
+                answers: [String::from("13140"), String::from("##..\n#<>.\n")],
+                cached: [false, true],
+                timings: Timings {
+                    input: Duration::from_micros(5),
+                    parts: [Duration::from_micros(10), Duration::from_micros(20)],
+                },
+                allocations: Some([
+                    AllocStats {
+                        allocations: 1,
+                        bytes: 8,
+                    },
+                    AllocStats::default(),
+                ]),
+            }],
+            machine: Machine {
+                os: String::from("linux"),
+                cpu: String::from("cpu"),
+                threads: 4,
+                rustc: String::from("rustc"),
+                profile: String::from("release"),
+            },
+            generated: 1_671_926_400,
+        }
+    }
+
+    #[test]
+    fn timestamps_are_utc() {
+        assert_eq!(timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(timestamp(1_671_926_400 + 3_661), "2022-12-25T01:01:01Z");
+        assert_eq!(timestamp(951_782_400), "2000-02-29T00:00:00Z");
+    }
+
+    #[test]
+    fn markdown_puts_ascii_art_in_code_blocks() {
+        let markdown = report().to_markdown();
+        assert!(markdown.starts_with("# Advent of Code 2022\n"));
+        assert!(markdown.contains("Generated 2022-12-25T00:00:00Z."));
+        assert!(markdown.contains("**Part 1:** `13140`"));
+        assert!(markdown.contains("```text\n##..\n#<>.\n```"));
+        assert!(markdown.contains("| 10 | 5.00µs | 10.00µs | 20.00µs | 35.00µs |"));
+        assert!(markdown.contains("- Part 1 allocated 1 times, 8 bytes"));
+        assert!(markdown.contains("| Profile | release |"));
+    }
+
+    #[test]
+    fn html_is_escaped() {
+        let html = report().to_html();
+        assert!(html.contains("<pre>##..\n#&lt;&gt;.</pre>"));
+        assert!(html.contains("<code>13140</code>"));
+        assert!(html.contains("<strong>Part 2 (cached):</strong>"));
+        assert!(html.ends_with("</html>\n"));
+    }
+
+    #[test]
+    fn format_follows_extension() {
+        assert_eq!(Format::from_extension(Some("html")), Format::Html);
+        assert_eq!(Format::from_extension(Some("md")), Format::Markdown);
+        assert_eq!("html".parse(), Ok(Format::Html));
+        assert!("pdf".parse::<Format>().is_err());
+    }
+}
@@ -0,0 +1,370 @@
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use serde_json::{json, Value};
+use tracing::{debug, info, warn};
+
+use crate::{solve_part, Day, Year};
+
+/// Largest input accepted, well above any real puzzle input.
+const MAX_BODY: usize = 16 * 1024 * 1024;
+/// How long a client may take to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Serves the year's solvers over HTTP until the process is stopped.
+///
+/// - `GET /days` lists the days that can be solved and their time budgets.
+/// - `POST /solve/{day}/{part}` solves the request body, answering with JSON holding the answer
+///   and how long it took.
+///
+/// Each connection gets its own thread. A solver that runs past its day's budget is answered
+/// with 504, and keeps running in the background until it finishes. Only as many solvers as there
+/// are cores run at once, counting those still finishing, and requests beyond that get 503.
+pub fn serve(year: &'static Year, address: impl ToSocketAddrs) -> io::Result<()> {
+    let listener = TcpListener::bind(address)?;
+    info!(address = %listener.local_addr()?, "serving");
+    let solves = Solves::new(thread::available_parallelism().map_or(1, |cores| cores.get()));
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(error) => {
+                warn!(%error, "could not accept connection");
+                continue;
+            }
+        };
+        let solves = solves.clone();
+        thread::spawn(move || {
+            if let Err(error) = handle(year, &solves, stream) {
+                debug!(%error, "connection failed");
+            }
+        });
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Response {
+    status: u16,
+    body: Value,
+}
+
+impl Response {
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.into() }),
+        }
+    }
+
+    fn write_to(&self, out: &mut impl Write) -> io::Result<()> {
+        let body = self.body.to_string();
+        let reason = match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            500 => "Internal Server Error",
+            503 => "Service Unavailable",
+            504 => "Gateway Timeout",
+            _ => "",
+        };
+        write!(
+            out,
+            "HTTP/1.1 {} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+             Connection: close\r\n\r\n{body}",
+            self.status,
+            body.len()
+        )?;
+        out.flush()
+    }
+}
+
+/// The solvers running, including those whose clients have given up on them, and how many may.
+#[derive(Debug, Clone)]
+struct Solves {
+    running: Arc<AtomicUsize>,
+    limit: usize,
+}
+
+impl Solves {
+    fn new(limit: usize) -> Self {
+        Self {
+            running: Arc::new(AtomicUsize::new(0)),
+            limit,
+        }
+    }
+
+    /// Takes a free slot, which is given back when the returned guard is dropped.
+    fn start(&self) -> Option<Slot> {
+        self.running
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |running| {
+                (running < self.limit).then_some(running + 1)
+            })
+            .ok()?;
+        Some(Slot(Arc::clone(&self.running)))
+    }
+}
+
+struct Slot(Arc<AtomicUsize>);
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+fn handle(year: &Year, solves: &Solves, stream: TcpStream) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+    let response = match read_request(&mut reader) {
+        Ok(request) => {
+            debug!(method = request.method, path = request.path, "request");
+            route(year, solves, request)
+        }
+        Err(response) => response,
+    };
+    response.write_to(&mut &stream)
+}
+
+fn read_request(reader: &mut impl BufRead) -> Result<Request, Response> {
+    let bad_request = |_| Response::error(400, "malformed request");
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(bad_request)?;
+    let mut words = line.split_whitespace();
+    let (Some(method), Some(path)) = (words.next(), words.next()) else {
+        return Err(Response::error(400, "malformed request line"));
+    };
+    let (method, path) = (method.to_owned(), path.to_owned());
+
+    let mut length = 0;
+    loop {
+        line.clear();
+        reader.read_line(&mut line).map_err(bad_request)?;
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| Response::error(400, "bad content length"))?;
+            }
+        }
+    }
+    if length > MAX_BODY {
+        return Err(Response::error(413, "input too large"));
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).map_err(bad_request)?;
+    Ok(Request { method, path, body })
+}
+
+fn route(year: &Year, solves: &Solves, request: Request) -> Response {
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["days"]) => {
+            let days: Vec<Value> = year
+                .days()
+                .into_iter()
+                .map(|day| {
+                    let budget = year.get(day).unwrap().budget();
+                    json!({ "day": day, "budget_ms": budget.as_millis() as u64 })
+                })
+                .collect();
+            Response::ok(json!({ "year": year.year, "days": days }))
+        }
+        ("POST", ["solve", day, part]) => {
+            let Some((day, solver)) = day.parse().ok().and_then(|day| Some((day, year.get(day)?)))
+            else {
+                return Response::error(404, format!("there is no solver for day {day}"));
+            };
+            let part = match *part {
+                "1" => 1,
+                "2" => 2,
+                _ => return Response::error(404, format!("there is no part {part}")),
+            };
+            let Ok(input) = String::from_utf8(request.body) else {
+                return Response::error(400, "input is not UTF-8");
+            };
+            let Some(slot) = solves.start() else {
+                return Response::error(503, "every solver is busy, try again later");
+            };
+            match solve_within(solver, part, input, solver.budget(), slot) {
+                Ok((answer, time)) => Response::ok(json!({
+                    "year": year.year,
+                    "day": day,
+                    "part": part,
+                    "answer": answer,
+                    "time_ns": time.as_nanos() as u64,
+                })),
+                Err(response) => response,
+            }
+        }
+        (_, ["days"] | ["solve", _, _]) => Response::error(405, "method not allowed"),
+        _ => Response::error(404, "not found"),
+    }
+}
+
+/// Solves on another thread, holding `slot` until it finishes, but giving up waiting once the
+/// budget has passed.
+fn solve_within(
+    solver: &'static dyn Day,
+    part: u8,
+    input: String,
+    budget: Duration,
+    slot: Slot,
+) -> Result<(String, Duration), Response> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let _slot = slot;
+        let _ = sender.send(solve_part(solver, part, &input));
+    });
+    match receiver.recv_timeout(budget) {
+        Ok(Ok(solution)) => Ok((solution.answer, solution.time)),
+        Ok(Err(error)) => Err(Response::error(404, error.to_string())),
+        Err(mpsc::RecvTimeoutError::Timeout) => Err(Response::error(
+            504,
+            format!("no answer within the budget of {budget:?}"),
+        )),
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err(Response::error(500, "solver panicked")),
+    }
+}
+
+#[cfg(test)]
+mod serve_tests {
+    use super::*;
+    use std::io::Read;
+
+    struct Lengths;
+
+    impl Day for Lengths {
+        fn part_1(&self, input: &str) -> String {
+            input.len().to_string()
+        }
+
+        fn part_2(&self, input: &str) -> String {
+            if input.is_empty() {
+                panic!("empty input");
+            }
+            thread::sleep(Duration::from_millis(200));
+            input.lines().count().to_string()
+        }
+
+        fn budget(&self) -> Duration {
+            Duration::from_millis(50)
+        }
+    }
+
+    static YEAR: Year = Year {
+        year: 2022,
+        get: |day| (day == 4).then_some(&Lengths as &dyn Day),
+        inputs: "",
+        version: "0.1.0",
+        cache: "",
+        bundle: "",
+        history: "",
+        embedded: &[],
+    };
+
+    fn request(method: &str, path: &str, body: &str) -> Request {
+        Request {
+            method: method.to_owned(),
+            path: path.to_owned(),
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn reads_requests() {
+        let raw =
+            "POST /solve/4/1 HTTP/1.1\r\nHost: localhost\r\ncontent-length: 5\r\n\r\nab\ncdEXTRA";
+        let request = read_request(&mut raw.as_bytes()).unwrap();
+        assert_eq!(request, self::request("POST", "/solve/4/1", "ab\ncd"));
+        let too_large = format!(
+            "POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY + 1
+        );
+        assert_eq!(
+            read_request(&mut too_large.as_bytes()).unwrap_err().status,
+            413
+        );
+    }
+
+    #[test]
+    fn lists_days() {
+        let response = route(&YEAR, &Solves::new(1), request("GET", "/days", ""));
+        assert_eq!(
+            response.body,
+            json!({ "year": 2022, "days": [{ "day": 4, "budget_ms": 50 }] })
+        );
+    }
+
+    #[test]
+    fn solves_parts() {
+        let response = route(&YEAR, &Solves::new(1), request("POST", "/solve/4/1", "abc"));
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body["answer"], "3");
+        assert!(response.body["time_ns"].is_u64());
+    }
+
+    #[test]
+    fn reports_failures() {
+        let solves = Solves::new(4);
+        let status = |method, path, body| route(&YEAR, &solves, request(method, path, body)).status;
+        assert_eq!(status("POST", "/solve/5/1", ""), 404);
+        assert_eq!(status("POST", "/solve/4/3", ""), 404);
+        assert_eq!(status("GET", "/solve/4/1", ""), 405);
+        assert_eq!(status("POST", "/solve/4/2", "slow"), 504);
+        assert_eq!(status("POST", "/solve/4/2", ""), 500);
+    }
+
+    #[test]
+    fn limits_running_solvers() {
+        let solves = Solves::new(1);
+        let status = |body| route(&YEAR, &solves, request("POST", "/solve/4/2", body)).status;
+        assert_eq!(status("slow"), 504);
+        // the slow solver still holds the only slot
+        assert_eq!(status("fast"), 503);
+        thread::sleep(Duration::from_millis(400));
+        assert_eq!(solves.running.load(Ordering::SeqCst), 0);
+        assert_eq!(status(""), 500);
+        assert_eq!(solves.running.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn serves_over_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || handle(&YEAR, &Solves::new(1), listener.accept().unwrap().0));
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream
+            .write_all(b"POST /solve/4/1 HTTP/1.1\r\nContent-Length: 2\r\n\r\nhi")
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("\"answer\":\"2\""));
+    }
+}
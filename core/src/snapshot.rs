@@ -0,0 +1,106 @@
+use std::{fs, path::Path};
+
+/// Set to update the stored snapshots instead of comparing against them.
+pub const UPDATE_VARIABLE: &str = "AOC_UPDATE_SNAPSHOTS";
+
+/// Compares `actual` with the snapshot called `name` in `dir`, panicking with a diff if they differ.
+///
+/// With `AOC_UPDATE_SNAPSHOTS` set, the snapshot is written instead.
+#[track_caller]
+pub fn assert_snapshot(dir: &str, name: &str, actual: &str) {
+    check(
+        dir,
+        name,
+        actual,
+        std::env::var_os(UPDATE_VARIABLE).is_some(),
+    );
+}
+
+#[track_caller]
+fn check(dir: &str, name: &str, actual: &str, update: bool) {
+    let path = Path::new(dir).join(format!("{name}.txt"));
+    if update {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+    let Ok(expected) = fs::read_to_string(&path) else {
+        panic!(
+            "there is no snapshot at {}, run with {UPDATE_VARIABLE}=1 to create it",
+            path.display()
+        );
+    };
+    if expected != actual {
+        panic!(
+            "snapshot {name} does not match, run with {UPDATE_VARIABLE}=1 to accept the changes\n\
+             --- {}\n+++ actual\n{}",
+            path.display(),
+            diff(&expected, actual)
+        );
+    }
+}
+
+/// The lines of `expected` and `actual`, marked `-` when only in `expected` and `+` when only in
+/// `actual`.
+pub fn diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    // longest common subsequences of the remaining lines, from the end
+    let mut common = vec![vec![0; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            common[i][j] = if expected[i] == actual[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            out += &format!(" {}\n", expected[i]);
+            (i, j) = (i + 1, j + 1);
+        } else if j == actual.len() || (i < expected.len() && common[i + 1][j] >= common[i][j + 1])
+        {
+            out += &format!("-{}\n", expected[i]);
+            i += 1;
+        } else {
+            out += &format!("+{}\n", actual[j]);
+            j += 1;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod snapshot_tests {
+    use super::*;
+
+    #[test]
+    fn diffs_lines() {
+        assert_eq!(
+            diff("a\nb\nc\nd\n", "a\nc\nx\nd\ne\n"),
+            " a\n-b\n c\n+x\n d\n+e\n"
+        );
+        assert_eq!(diff("same\n", "same\n"), " same\n");
+    }
+
+    #[test]
+    fn compares_with_stored_snapshots() {
+        let dir = std::env::temp_dir().join(format!("aoc_snapshots_{}", std::process::id()));
+        let dir = dir.to_str().unwrap();
+        fs::create_dir_all(dir).unwrap();
+        fs::write(Path::new(dir).join("grid.txt"), "#.\n.#\n").unwrap();
+        check(dir, "grid", "#.\n.#\n", false);
+        let mismatch = std::panic::catch_unwind(|| check(dir, "grid", "#.\n##\n", false));
+        let message = *mismatch.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.ends_with(" #.\n-.#\n+##\n"));
+        assert!(std::panic::catch_unwind(|| check(dir, "missing", "", false)).is_err());
+        check(dir, "grid", "#.\n##\n", true);
+        check(dir, "grid", "#.\n##\n", false);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,102 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+    time::Instant,
+};
+
+use crate::Solution;
+
+/// A day that can solve its input while reading it, holding only a bounded part of it in memory.
+pub trait Stream: Sync {
+    fn stream_part_1(&self, input: &mut dyn BufRead) -> io::Result<String>;
+    fn stream_part_2(&self, input: &mut dyn BufRead) -> io::Result<String>;
+}
+
+/// Solves one part, reading the input from `input` as it goes.
+pub fn solve(stream: &dyn Stream, part: u8, input: &mut dyn BufRead) -> io::Result<Solution> {
+    let start = Instant::now();
+    let answer = match part {
+        1 => stream.stream_part_1(input)?,
+        2 => stream.stream_part_2(input)?,
+        _ => {
+            let message = format!("there is no part {part}");
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, message));
+        }
+    };
+    Ok(Solution {
+        answer,
+        time: start.elapsed(),
+    })
+}
+
+/// Solves one part, streaming the input from the file at `path`.
+pub fn solve_file(stream: &dyn Stream, part: u8, path: &Path) -> io::Result<Solution> {
+    solve(stream, part, &mut BufReader::new(File::open(path)?))
+}
+
+/// Calls `line` with each line of `input`, reusing one buffer, and without the line ending.
+pub fn for_each_line(
+    input: &mut dyn BufRead,
+    mut line: impl FnMut(&str) -> io::Result<()>,
+) -> io::Result<()> {
+    let mut buffer = String::new();
+    loop {
+        buffer.clear();
+        if input.read_line(&mut buffer)? == 0 {
+            return Ok(());
+        }
+        line(buffer.trim_end_matches(['\n', '\r']))?;
+    }
+}
+
+/// An error for input that does not hold what the day expects.
+pub fn invalid(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+#[cfg(test)]
+mod stream_tests {
+    use super::*;
+
+    struct Longest;
+
+    impl Stream for Longest {
+        fn stream_part_1(&self, input: &mut dyn BufRead) -> io::Result<String> {
+            let mut longest = 0;
+            for_each_line(input, |line| {
+                longest = longest.max(line.len());
+                Ok(())
+            })?;
+            Ok(longest.to_string())
+        }
+
+        fn stream_part_2(&self, input: &mut dyn BufRead) -> io::Result<String> {
+            let mut lines = 0;
+            for_each_line(input, |line| match line {
+                "stop" => Err(invalid("stopped")),
+                _ => {
+                    lines += 1;
+                    Ok(())
+                }
+            })?;
+            Ok(lines.to_string())
+        }
+    }
+
+    #[test]
+    fn reads_lines_without_their_endings() {
+        let solution = solve(&Longest, 1, &mut "ab\r\nabcd\nabc".as_bytes()).unwrap();
+        assert_eq!(solution.answer, "4");
+        let solution = solve(&Longest, 2, &mut "ab\n\nabc\n".as_bytes()).unwrap();
+        assert_eq!(solution.answer, "3");
+    }
+
+    #[test]
+    fn stops_at_errors() {
+        let error = solve(&Longest, 2, &mut "a\nstop\nb\n".as_bytes()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        let error = solve(&Longest, 3, &mut "a\n".as_bytes()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,162 @@
+use std::{
+    fmt::Display,
+    io::{stdout, IsTerminal},
+    sync::OnceLock,
+};
+
+use owo_colors::OwoColorize;
+
+pub type Rgb = (u8, u8, u8);
+
+/// What a piece of output is, so that the theme can decide how it looks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Header,
+    Label,
+    Answer,
+    Timing,
+    Error,
+    Separator,
+}
+
+/// Colours for each role. Roles without a colour are printed as plain text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub header: Option<Rgb>,
+    pub label: Option<Rgb>,
+    pub answer: Option<Rgb>,
+    pub timing: Option<Rgb>,
+    pub error: Option<Rgb>,
+    pub separator: Option<Rgb>,
+}
+
+impl Theme {
+    /// Catppuccin Frappé, for dark terminals.
+    pub const DARK: Self = Self {
+        header: Some((198, 208, 245)),
+        label: Some((181, 191, 226)),
+        answer: Some((166, 209, 137)),
+        timing: Some((186, 187, 241)),
+        error: Some((231, 130, 132)),
+        separator: Some((115, 121, 148)),
+    };
+
+    /// Catppuccin Latte, for light terminals.
+    pub const LIGHT: Self = Self {
+        header: Some((76, 79, 105)),
+        label: Some((92, 95, 119)),
+        answer: Some((64, 160, 43)),
+        timing: Some((114, 135, 253)),
+        error: Some((210, 15, 57)),
+        separator: Some((156, 160, 176)),
+    };
+
+    /// No colours, and so no escape codes.
+    pub const MONOCHROME: Self = Self {
+        header: None,
+        label: None,
+        answer: None,
+        timing: None,
+        error: None,
+        separator: None,
+    };
+
+    pub fn named(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::DARK),
+            "light" => Some(Self::LIGHT),
+            "monochrome" | "none" => Some(Self::MONOCHROME),
+            _ => None,
+        }
+    }
+
+    /// Picks a theme from the environment.
+    ///
+    /// Colour is turned off when `NO_COLOR` is set or stdout is not a terminal. Otherwise
+    /// `AOC_THEME` names the theme, defaulting to dark.
+    pub fn detect() -> Self {
+        Self::detect_from(
+            std::env::var("NO_COLOR").ok(),
+            stdout().is_terminal(),
+            std::env::var("AOC_THEME").ok(),
+        )
+    }
+
+    fn detect_from(no_color: Option<String>, is_terminal: bool, name: Option<String>) -> Self {
+        if no_color.is_some_and(|value| !value.is_empty()) || !is_terminal {
+            return Self::MONOCHROME;
+        }
+        name.and_then(|name| Self::named(&name))
+            .unwrap_or(Self::DARK)
+    }
+
+    fn colour(&self, role: Role) -> Option<Rgb> {
+        match role {
+            Role::Header => self.header,
+            Role::Label => self.label,
+            Role::Answer => self.answer,
+            Role::Timing => self.timing,
+            Role::Error => self.error,
+            Role::Separator => self.separator,
+        }
+    }
+
+    pub fn paint(&self, role: Role, text: impl Display) -> String {
+        match self.colour(role) {
+            Some((r, g, b)) => text.truecolor(r, g, b).to_string(),
+            None => text.to_string(),
+        }
+    }
+}
+
+static CURRENT: OnceLock<Theme> = OnceLock::new();
+
+/// The theme used for output, detected from the environment unless [`set`] was called first.
+pub fn current() -> &'static Theme {
+    CURRENT.get_or_init(Theme::detect)
+}
+
+/// Chooses the theme for output. Has no effect once anything has been printed.
+pub fn set(theme: Theme) {
+    let _ = CURRENT.set(theme);
+}
+
+/// Paints the text in the current theme.
+pub fn paint(role: Role, text: impl Display) -> String {
+    current().paint(role, text)
+}
+
+#[cfg(test)]
+mod theme_tests {
+    use super::*;
+
+    #[test]
+    fn monochrome_has_no_escape_codes() {
+        assert_eq!(Theme::MONOCHROME.paint(Role::Answer, 42), "42");
+        assert_eq!(
+            Theme::DARK.paint(Role::Answer, 42),
+            "\x1b[38;2;166;209;137m42\x1b[39m"
+        );
+    }
+
+    #[test]
+    fn detection_honours_no_color_and_terminals() {
+        let dark = Some(String::from("dark"));
+        let light = Some(String::from("light"));
+        assert_eq!(
+            Theme::detect_from(Some(String::from("1")), true, dark.clone()),
+            Theme::MONOCHROME
+        );
+        assert_eq!(Theme::detect_from(None, false, dark), Theme::MONOCHROME);
+        // an empty NO_COLOR does not count
+        assert_eq!(
+            Theme::detect_from(Some(String::new()), true, light),
+            Theme::LIGHT
+        );
+        assert_eq!(
+            Theme::detect_from(None, true, Some(String::from("unknown"))),
+            Theme::DARK
+        );
+        assert_eq!(Theme::detect_from(None, true, None), Theme::DARK);
+    }
+}
@@ -0,0 +1,203 @@
+use std::{
+    fmt::Display,
+    io::{self, Write},
+};
+
+use crossterm::{cursor, queue, style, terminal};
+
+pub mod export;
+mod font;
+pub mod player;
+
+/// Point on a frame's grid, as (column, row).
+pub type Point = (usize, usize);
+
+/// How an overlay should stand out from the grid underneath it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Highlight {
+    /// The thing being simulated, e.g. the expedition or the head of the rope.
+    Primary,
+    /// Supporting state, e.g. the rest of the rope or the explored area.
+    Secondary,
+    /// Background history, e.g. a trail of visited points.
+    Muted,
+}
+
+/// A glyph drawn on top of a frame's grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overlay {
+    pub point: Point,
+    pub glyph: char,
+    pub highlight: Highlight,
+}
+
+/// A single step of a simulation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Frame {
+    pub grid: Vec<Vec<char>>,
+    pub overlays: Vec<Overlay>,
+    pub caption: String,
+}
+
+impl Frame {
+    pub fn new(grid: Vec<Vec<char>>) -> Self {
+        Self {
+            grid,
+            overlays: Vec::new(),
+            caption: String::new(),
+        }
+    }
+
+    /// Builds the grid from text, one row per line.
+    pub fn from_text(text: &str) -> Self {
+        Self::new(text.lines().map(|line| line.chars().collect()).collect())
+    }
+
+    pub fn with_overlay(mut self, point: Point, glyph: char, highlight: Highlight) -> Self {
+        self.overlay(point, glyph, highlight);
+        self
+    }
+
+    pub fn with_caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = caption.into();
+        self
+    }
+
+    /// Adds an overlay. Points outside of the grid are ignored when drawing.
+    pub fn overlay(&mut self, point: Point, glyph: char, highlight: Highlight) {
+        self.overlays.push(Overlay {
+            point,
+            glyph,
+            highlight,
+        });
+    }
+
+    pub fn width(&self) -> usize {
+        self.grid.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    pub fn height(&self) -> usize {
+        self.grid.len()
+    }
+
+    /// The grid with the overlays applied. Later overlays win over earlier ones.
+    pub fn cells(&self) -> Vec<Vec<(char, Option<Highlight>)>> {
+        let mut cells: Vec<Vec<_>> = self
+            .grid
+            .iter()
+            .map(|row| row.iter().map(|&c| (c, None)).collect())
+            .collect();
+        for overlay in &self.overlays {
+            let (x, y) = overlay.point;
+            if let Some(cell) = cells.get_mut(y).and_then(|row| row.get_mut(x)) {
+                *cell = (overlay.glyph, Some(overlay.highlight));
+            }
+        }
+        cells
+    }
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.cells() {
+            let line: String = row.into_iter().map(|(c, _)| c).collect();
+            writeln!(f, "{line}")?;
+        }
+        if !self.caption.is_empty() {
+            writeln!(f, "{}", self.caption)?;
+        }
+        Ok(())
+    }
+}
+
+/// Receives the frames emitted by a simulation.
+pub trait FrameSink {
+    /// Whether frames are wanted at all.
+    ///
+    /// Solvers check this before building a frame so that normal runs do not pay for it.
+    fn is_recording(&self) -> bool {
+        true
+    }
+
+    fn emit(&mut self, frame: Frame);
+}
+
+/// Discards every frame. Used when solving normally.
+pub struct NoFrames;
+
+impl FrameSink for NoFrames {
+    fn is_recording(&self) -> bool {
+        false
+    }
+
+    fn emit(&mut self, _frame: Frame) {}
+}
+
+impl FrameSink for Vec<Frame> {
+    fn emit(&mut self, frame: Frame) {
+        self.push(frame);
+    }
+}
+
+/// A day whose simulation can be watched.
+pub trait Visualize: Sync {
+    /// Runs the simulation on the input, emitting frames as it goes.
+    fn visualize(&self, input: &str, sink: &mut dyn FrameSink);
+}
+
+/// Draws the frame from the top left of the terminal, clearing whatever is left below it.
+fn draw<W: Write>(out: &mut W, frame: &Frame) -> io::Result<()> {
+    let palette = export::Palette::default();
+    queue!(out, cursor::MoveTo(0, 0))?;
+    for row in frame.cells() {
+        let mut current = None;
+        for (c, highlight) in row {
+            if current != Some(highlight) {
+                let (r, g, b) = palette.colour(highlight);
+                queue!(
+                    out,
+                    style::SetForegroundColor(style::Color::Rgb { r, g, b })
+                )?;
+                current = Some(highlight);
+            }
+            queue!(out, style::Print(c))?;
+        }
+        queue!(
+            out,
+            terminal::Clear(terminal::ClearType::UntilNewLine),
+            style::Print("\r\n")
+        )?;
+    }
+    queue!(
+        out,
+        style::ResetColor,
+        style::Print(&frame.caption),
+        terminal::Clear(terminal::ClearType::UntilNewLine),
+        style::Print("\r\n"),
+        terminal::Clear(terminal::ClearType::FromCursorDown)
+    )
+}
+
+#[cfg(test)]
+mod visualize_tests {
+    use super::*;
+
+    #[test]
+    fn overlays_replace_cells() {
+        let frame = Frame::from_text("...\n...")
+            .with_overlay((1, 0), '#', Highlight::Primary)
+            .with_overlay((2, 1), '@', Highlight::Muted)
+            .with_overlay((5, 5), '!', Highlight::Primary);
+        assert_eq!(frame.to_string(), ".#.\n..@\n");
+        assert_eq!(frame.cells()[0][1], ('#', Some(Highlight::Primary)));
+        assert_eq!(frame.cells()[0][0], ('.', None));
+    }
+
+    #[test]
+    fn caption_follows_grid() {
+        let frame = Frame::from_text("ab").with_caption("minute 1");
+        assert_eq!(frame.to_string(), "ab\nminute 1\n");
+        assert_eq!(frame.width(), 2);
+        assert_eq!(frame.height(), 1);
+    }
+}
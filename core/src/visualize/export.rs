@@ -0,0 +1,408 @@
+use std::{
+    borrow::Cow,
+    fmt::Write as _,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use super::{
+    font::{self, GLYPH_HEIGHT, GLYPH_WIDTH},
+    Frame, Highlight,
+};
+
+/// Pixels between glyphs, horizontally and vertically.
+const CELL_WIDTH: usize = GLYPH_WIDTH + 1;
+const CELL_HEIGHT: usize = GLYPH_HEIGHT + 2;
+
+pub type Rgb = (u8, u8, u8);
+
+/// Colours used when exporting, by role.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    pub background: Rgb,
+    pub text: Rgb,
+    pub primary: Rgb,
+    pub secondary: Rgb,
+    pub muted: Rgb,
+}
+
+impl Palette {
+    pub(super) fn colour(&self, highlight: Option<Highlight>) -> Rgb {
+        match highlight {
+            None => self.text,
+            Some(Highlight::Primary) => self.primary,
+            Some(Highlight::Secondary) => self.secondary,
+            Some(Highlight::Muted) => self.muted,
+        }
+    }
+
+    /// Index of the colour within [`Palette::to_rgb`].
+    fn index(highlight: Option<Highlight>) -> u8 {
+        match highlight {
+            None => 1,
+            Some(Highlight::Primary) => 2,
+            Some(Highlight::Secondary) => 3,
+            Some(Highlight::Muted) => 4,
+        }
+    }
+
+    fn to_rgb(self) -> Vec<u8> {
+        [
+            self.background,
+            self.text,
+            self.primary,
+            self.secondary,
+            self.muted,
+        ]
+        .into_iter()
+        .flat_map(|(r, g, b)| [r, g, b])
+        .collect()
+    }
+}
+
+impl Default for Palette {
+    /// The same colours as the terminal renderer, on a dark background.
+    fn default() -> Self {
+        Self {
+            background: (48, 52, 70),
+            text: (181, 191, 226),
+            primary: (166, 209, 137),
+            secondary: (140, 170, 238),
+            muted: (115, 121, 148),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParsePaletteError(String);
+
+impl std::fmt::Display for ParsePaletteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid palette: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParsePaletteError {}
+
+impl FromStr for Palette {
+    type Err = ParsePaletteError;
+
+    /// Parses five comma separated hex colours: background, text, primary, secondary, muted.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let colours = s
+            .split(',')
+            .map(|colour| parse_hex(colour.trim()))
+            .collect::<Result<Vec<_>, _>>()?;
+        match colours.as_slice() {
+            &[background, text, primary, secondary, muted] => Ok(Self {
+                background,
+                text,
+                primary,
+                secondary,
+                muted,
+            }),
+            _ => Err(ParsePaletteError(format!(
+                "expected 5 colours, found {}",
+                colours.len()
+            ))),
+        }
+    }
+}
+
+fn parse_hex(colour: &str) -> Result<Rgb, ParsePaletteError> {
+    let hex = colour.strip_prefix('#').unwrap_or(colour);
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ParsePaletteError(format!("`{colour}` is not a hex colour")));
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap();
+    Ok((channel(0), channel(2), channel(4)))
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    /// How long each frame is shown.
+    pub delay: Duration,
+    /// Extra time the last frame is held for.
+    pub hold: Duration,
+    pub palette: Palette,
+    /// Size of a font pixel in image pixels.
+    pub scale: usize,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            delay: Duration::from_millis(100),
+            hold: Duration::from_secs(2),
+            palette: Palette::default(),
+            scale: 2,
+        }
+    }
+}
+
+impl ExportOptions {
+    fn delay_of(&self, index: usize, count: usize) -> Duration {
+        if index + 1 == count {
+            self.delay + self.hold
+        } else {
+            self.delay
+        }
+    }
+}
+
+/// Writes the frames as an asciinema v2 recording.
+pub fn write_asciicast<W: Write>(
+    frames: &[Frame],
+    options: &ExportOptions,
+    mut writer: W,
+) -> io::Result<()> {
+    let (columns, rows) = terminal_size(frames);
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs());
+    writeln!(
+        writer,
+        "{{\"version\": 2, \"width\": {columns}, \"height\": {rows}, \"timestamp\": {timestamp}}}"
+    )?;
+
+    let mut time = Duration::ZERO;
+    for (index, frame) in frames.iter().enumerate() {
+        let output = serde_json::to_string(&ansi(frame, &options.palette))?;
+        writeln!(writer, "[{:.6}, \"o\", {output}]", time.as_secs_f64())?;
+        time += options.delay_of(index, frames.len());
+    }
+    // an empty event keeps the last frame on screen for its delay
+    writeln!(writer, "[{:.6}, \"o\", \"\"]", time.as_secs_f64())?;
+    writer.flush()
+}
+
+/// Columns and rows needed to show every frame, including captions.
+fn terminal_size(frames: &[Frame]) -> (usize, usize) {
+    frames.iter().fold((0, 0), |(columns, rows), frame| {
+        let caption = frame.caption.lines().count();
+        let width = frame
+            .caption
+            .lines()
+            .map(|line| line.chars().count())
+            .fold(frame.width(), usize::max);
+        (columns.max(width), rows.max(frame.height() + caption))
+    })
+}
+
+/// Draws the frame from the top left of a cleared screen, with 24-bit colour escape codes.
+fn ansi(frame: &Frame, palette: &Palette) -> String {
+    let mut out = String::from("\x1b[H\x1b[2J");
+    for row in frame.cells() {
+        let mut current = None;
+        for (c, highlight) in row {
+            if current != Some(highlight) {
+                let (r, g, b) = palette.colour(highlight);
+                write!(out, "\x1b[38;2;{r};{g};{b}m").unwrap();
+                current = Some(highlight);
+            }
+            out.push(c);
+        }
+        out.push_str("\x1b[0m\r\n");
+    }
+    for line in frame.caption.lines() {
+        out.push_str(line);
+        out.push_str("\r\n");
+    }
+    out
+}
+
+/// Frames drawn with the built-in font, as indices into the palette.
+struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    /// Sized to fit every frame so that they can share a single canvas size.
+    fn sized_for(frames: &[Frame], scale: usize) -> Self {
+        let (columns, rows) = terminal_size(frames);
+        let width = (columns * CELL_WIDTH + 1) * scale;
+        let height = (rows * CELL_HEIGHT + 1) * scale;
+        Self {
+            width,
+            height,
+            pixels: vec![0; width * height],
+        }
+    }
+
+    fn draw(&mut self, frame: &Frame, scale: usize) {
+        self.pixels.fill(0);
+        for (y, row) in frame.cells().into_iter().enumerate() {
+            for (x, (c, highlight)) in row.into_iter().enumerate() {
+                self.draw_glyph(c, (x, y), Palette::index(highlight), scale);
+            }
+        }
+        for (line, text) in frame.caption.lines().enumerate() {
+            for (x, c) in text.chars().enumerate() {
+                self.draw_glyph(c, (x, frame.height() + line), 1, scale);
+            }
+        }
+    }
+
+    fn draw_glyph(&mut self, c: char, (column, row): (usize, usize), colour: u8, scale: usize) {
+        let left = (column * CELL_WIDTH + 1) * scale;
+        let top = (row * CELL_HEIGHT + 1) * scale;
+        for gy in 0..GLYPH_HEIGHT {
+            for gx in 0..GLYPH_WIDTH {
+                if !font::is_set(c, gx, gy) {
+                    continue;
+                }
+                for py in 0..scale {
+                    let start = (top + gy * scale + py) * self.width + left + gx * scale;
+                    if let Some(pixels) = self.pixels.get_mut(start..start + scale) {
+                        pixels.fill(colour);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn encoding_error(error: impl std::error::Error + Send + Sync + 'static) -> io::Error {
+    io::Error::other(error)
+}
+
+/// Writes the frames as an endlessly looping animated GIF.
+pub fn write_gif<W: Write>(frames: &[Frame], options: &ExportOptions, writer: W) -> io::Result<()> {
+    let mut canvas = Canvas::sized_for(frames, options.scale);
+    let too_large = || io::Error::new(io::ErrorKind::InvalidInput, "frames too large for a GIF");
+    let width = u16::try_from(canvas.width).map_err(|_| too_large())?;
+    let height = u16::try_from(canvas.height).map_err(|_| too_large())?;
+
+    let palette = options.palette.to_rgb();
+    let mut encoder = gif::Encoder::new(writer, width, height, &palette).map_err(encoding_error)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(encoding_error)?;
+    for (index, frame) in frames.iter().enumerate() {
+        canvas.draw(frame, options.scale);
+        let delay = options.delay_of(index, frames.len()).as_millis() / 10;
+        let gif_frame = gif::Frame {
+            width,
+            height,
+            delay: u16::try_from(delay).unwrap_or(u16::MAX),
+            buffer: Cow::Borrowed(&canvas.pixels),
+            ..gif::Frame::default()
+        };
+        encoder.write_frame(&gif_frame).map_err(encoding_error)?;
+    }
+    Ok(())
+}
+
+/// Writes one numbered PNG per frame into `dir`, returning their paths.
+///
+/// PNG sequences have no timing, so the delays are not used.
+pub fn write_png_sequence(
+    frames: &[Frame],
+    options: &ExportOptions,
+    dir: &Path,
+) -> io::Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(dir)?;
+    let mut canvas = Canvas::sized_for(frames, options.scale);
+    let palette = options.palette.to_rgb();
+    let digits = frames.len().to_string().len().max(4);
+    let mut paths = Vec::with_capacity(frames.len());
+    for (index, frame) in frames.iter().enumerate() {
+        canvas.draw(frame, options.scale);
+        let path = dir.join(format!("frame_{index:0digits$}.png"));
+        write_png(&canvas, &palette, BufWriter::new(File::create(&path)?))?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+fn write_png<W: Write>(canvas: &Canvas, palette: &[u8], writer: W) -> io::Result<()> {
+    let mut encoder = png::Encoder::new(writer, canvas.width as u32, canvas.height as u32);
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(palette);
+    let mut writer = encoder.write_header().map_err(encoding_error)?;
+    writer
+        .write_image_data(&canvas.pixels)
+        .map_err(encoding_error)
+}
+
+#[cfg(test)]
+mod export_tests {
+    use super::*;
+
+    fn frames() -> Vec<Frame> {
+        vec![
+            Frame::from_text("#.\n..").with_caption("one"),
+            Frame::from_text("..\n.#")
+                .with_overlay((0, 0), 'E', Highlight::Primary)
+                .with_caption("two"),
+        ]
+    }
+
+    #[test]
+    fn palette_parses() {
+        let palette: Palette = "#000000,ffffff,#ff0000,00ff00,#0000FF".parse().unwrap();
+        assert_eq!(palette.background, (0, 0, 0));
+        assert_eq!(palette.text, (255, 255, 255));
+        assert_eq!(palette.muted, (0, 0, 255));
+        assert!("#000000,#ffffff".parse::<Palette>().is_err());
+        assert!("#00000g,#ffffff,#ff0000,#00ff00,#0000ff"
+            .parse::<Palette>()
+            .is_err());
+    }
+
+    #[test]
+    fn asciicast_has_header_and_events() {
+        let options = ExportOptions {
+            delay: Duration::from_millis(250),
+            hold: Duration::from_secs(1),
+            ..ExportOptions::default()
+        };
+        let mut out = Vec::new();
+        write_asciicast(&frames(), &options, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("{\"version\": 2, \"width\": 3, \"height\": 3,"));
+        assert!(lines[1].starts_with("[0.000000, \"o\", \"\\u001b[H\\u001b[2J"));
+        assert!(lines[2].starts_with("[0.250000, \"o\","));
+        assert!(lines[2].contains("two\\r\\n"));
+        assert_eq!(lines[3], "[1.500000, \"o\", \"\"]");
+    }
+
+    #[test]
+    fn canvas_draws_glyphs_in_their_colour() {
+        let frames = frames();
+        let mut canvas = Canvas::sized_for(&frames, 1);
+        assert_eq!(canvas.width, 3 * CELL_WIDTH + 1);
+        assert_eq!(canvas.height, 3 * CELL_HEIGHT + 1);
+
+        canvas.draw(&frames[1], 1);
+        // top row of the 'E' overlay
+        let top = canvas.width + 1;
+        assert_eq!(&canvas.pixels[top..top + GLYPH_WIDTH], &[2; GLYPH_WIDTH]);
+        // the second '.' of the first row is left blank above its dot
+        let blank = canvas.width + 1 + CELL_WIDTH;
+        assert_eq!(
+            &canvas.pixels[blank..blank + GLYPH_WIDTH],
+            &[0; GLYPH_WIDTH]
+        );
+    }
+
+    #[test]
+    fn images_encode() {
+        let mut gif = Vec::new();
+        write_gif(&frames(), &ExportOptions::default(), &mut gif).unwrap();
+        assert!(gif.starts_with(b"GIF89a"));
+
+        let canvas = Canvas::sized_for(&frames(), 1);
+        let mut png = Vec::new();
+        write_png(&canvas, &Palette::default().to_rgb(), &mut png).unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+    }
+}
@@ -0,0 +1,350 @@
+use std::{
+    io::{self, stdout, Write},
+    sync::mpsc::{self, Receiver, Sender, TryRecvError},
+    time::Duration,
+};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute, queue, style, terminal,
+};
+
+use super::{draw, Frame, FrameSink, Visualize};
+
+const MIN_DELAY: Duration = Duration::from_millis(1);
+const MAX_DELAY: Duration = Duration::from_secs(5);
+/// How often the screen is refreshed while nothing is playing.
+const IDLE_POLL: Duration = Duration::from_millis(100);
+/// Frames taken from the solver per refresh, so a fast solver cannot starve the keyboard.
+const MAX_RECEIVED: usize = 1_000;
+
+const HELP: &str = "space pause, ←/→ step, ↑/↓ speed, g seek, home/end, q quit";
+
+/// Sends frames to the player, and stops building them once the player has quit.
+struct ChannelSink {
+    sender: Sender<Frame>,
+    open: bool,
+}
+
+impl FrameSink for ChannelSink {
+    fn is_recording(&self) -> bool {
+        self.open
+    }
+
+    fn emit(&mut self, frame: Frame) {
+        self.open = self.sender.send(frame).is_ok();
+    }
+}
+
+/// Playback state over the frames buffered so far.
+#[derive(Debug)]
+struct Playback {
+    frames: Vec<Frame>,
+    /// Whether the solver has emitted its last frame.
+    finished: bool,
+    position: usize,
+    paused: bool,
+    delay: Duration,
+    /// Digits typed after `g`, while choosing a frame to seek to.
+    seek: Option<String>,
+    /// Frame that was sought before the solver produced it.
+    pending: Option<usize>,
+}
+
+impl Playback {
+    fn new(delay: Duration) -> Self {
+        Self {
+            frames: Vec::new(),
+            finished: false,
+            position: 0,
+            paused: false,
+            delay: delay.clamp(MIN_DELAY, MAX_DELAY),
+            seek: None,
+            pending: None,
+        }
+    }
+
+    fn push(&mut self, frame: Frame) {
+        self.frames.push(frame);
+        if self.pending == Some(self.frames.len() - 1) {
+            self.position = self.frames.len() - 1;
+            self.pending = None;
+        }
+    }
+
+    fn finish(&mut self) {
+        self.finished = true;
+        if self.pending.take().is_some() {
+            self.position = self.last();
+        }
+    }
+
+    fn current(&self) -> Option<&Frame> {
+        self.frames.get(self.position)
+    }
+
+    fn last(&self) -> usize {
+        self.frames.len().saturating_sub(1)
+    }
+
+    fn is_playing(&self) -> bool {
+        !self.paused && self.seek.is_none() && self.pending.is_none()
+    }
+
+    /// Moves on to the next frame once the delay has passed.
+    fn tick(&mut self) {
+        if self.is_playing() && self.position < self.last() {
+            self.position += 1;
+        }
+    }
+
+    fn jump(&mut self, target: usize) {
+        if target < self.frames.len() || self.finished {
+            self.position = target.min(self.last());
+            self.pending = None;
+        } else {
+            self.position = self.last();
+            self.pending = Some(target);
+        }
+    }
+
+    /// Applies a key press, returning false to quit.
+    fn handle(&mut self, key: KeyCode) -> bool {
+        if let Some(seek) = &mut self.seek {
+            match key {
+                KeyCode::Char(c) if c.is_ascii_digit() => seek.push(c),
+                KeyCode::Backspace => {
+                    seek.pop();
+                }
+                KeyCode::Enter => {
+                    if let Ok(target) = seek.parse() {
+                        self.paused = true;
+                        self.jump(target);
+                    }
+                    self.seek = None;
+                }
+                KeyCode::Esc => self.seek = None,
+                _ => (),
+            }
+            return true;
+        }
+
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char(' ') => self.paused = !self.paused,
+            KeyCode::Right | KeyCode::Char('l' | '.') => {
+                self.paused = true;
+                self.position = (self.position + 1).min(self.last());
+            }
+            KeyCode::Left | KeyCode::Char('h' | ',') => {
+                self.paused = true;
+                self.position = self.position.saturating_sub(1);
+            }
+            KeyCode::Up | KeyCode::Char('+' | '=') => {
+                self.delay = (self.delay / 2).max(MIN_DELAY);
+            }
+            KeyCode::Down | KeyCode::Char('-') => {
+                self.delay = (self.delay * 2).min(MAX_DELAY);
+            }
+            KeyCode::Home => self.jump(0),
+            KeyCode::End => self.jump(self.last()),
+            KeyCode::Char('g') => self.seek = Some(String::new()),
+            _ => (),
+        }
+        true
+    }
+
+    fn status(&self) -> String {
+        if let Some(seek) = &self.seek {
+            return format!("seek to frame: {seek}_ (enter to jump, esc to cancel)");
+        }
+        let buffering = if self.finished { "" } else { "+" };
+        let state = match self.pending {
+            Some(target) => format!("waiting for frame {target}"),
+            None if self.paused => String::from("paused"),
+            None => String::from("playing"),
+        };
+        format!(
+            "frame {}/{}{buffering} | {} ms | {state} | {HELP}",
+            self.position,
+            self.last(),
+            self.delay.as_millis(),
+        )
+    }
+}
+
+/// Puts the terminal into raw mode on an alternate screen until dropped.
+struct RawTerminal;
+
+impl RawTerminal {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _ = execute!(stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Plays the visualization with keyboard controls, starting `delay` apart.
+///
+/// The simulation runs in the background while its frames are buffered, so playback can step
+/// back to any frame that has already been shown.
+pub fn play(visualizer: &dyn Visualize, input: &str, delay: Duration) -> io::Result<()> {
+    let (sender, receiver) = mpsc::channel();
+    std::thread::scope(|scope| {
+        scope.spawn(move || {
+            let mut sink = ChannelSink { sender, open: true };
+            visualizer.visualize(input, &mut sink);
+        });
+        let _terminal = RawTerminal::enter()?;
+        run(receiver, Playback::new(delay))
+    })
+}
+
+/// Returns when the user quits, dropping the receiver so the solver stops sending frames.
+fn run(receiver: Receiver<Frame>, mut playback: Playback) -> io::Result<()> {
+    let mut out = stdout();
+    loop {
+        for _ in 0..MAX_RECEIVED {
+            match receiver.try_recv() {
+                Ok(frame) => playback.push(frame),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    playback.finish();
+                    break;
+                }
+            }
+        }
+
+        match playback.current() {
+            Some(frame) => draw(&mut out, frame)?,
+            None => queue!(out, cursor::MoveTo(0, 0))?,
+        }
+        queue!(
+            out,
+            style::Print(playback.status()),
+            terminal::Clear(terminal::ClearType::UntilNewLine)
+        )?;
+        out.flush()?;
+
+        let timeout = if playback.is_playing() {
+            playback.delay
+        } else {
+            IDLE_POLL
+        };
+        if !event::poll(timeout)? {
+            playback.tick();
+            continue;
+        }
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Release {
+                continue;
+            }
+            let interrupted =
+                key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c');
+            if interrupted || !playback.handle(key.code) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod player_tests {
+    use super::*;
+
+    fn playback(frames: usize) -> Playback {
+        let mut playback = Playback::new(Duration::from_millis(100));
+        for n in 0..frames {
+            playback.push(Frame::from_text(&n.to_string()));
+        }
+        playback
+    }
+
+    #[test]
+    fn plays_until_the_last_buffered_frame() {
+        let mut playback = playback(3);
+        playback.tick();
+        playback.tick();
+        playback.tick();
+        assert_eq!(playback.position, 2);
+        assert_eq!(playback.current().unwrap().to_string(), "2\n");
+    }
+
+    #[test]
+    fn stepping_pauses() {
+        let mut playback = playback(3);
+        playback.handle(KeyCode::Right);
+        assert!(playback.paused);
+        playback.tick();
+        assert_eq!(playback.position, 1);
+        playback.handle(KeyCode::Left);
+        playback.handle(KeyCode::Left);
+        assert_eq!(playback.position, 0);
+        playback.handle(KeyCode::Char(' '));
+        assert!(playback.is_playing());
+    }
+
+    #[test]
+    fn speed_is_clamped() {
+        let mut playback = playback(1);
+        playback.handle(KeyCode::Up);
+        assert_eq!(playback.delay, Duration::from_millis(50));
+        for _ in 0..20 {
+            playback.handle(KeyCode::Down);
+        }
+        assert_eq!(playback.delay, MAX_DELAY);
+        for _ in 0..30 {
+            playback.handle(KeyCode::Up);
+        }
+        assert_eq!(playback.delay, MIN_DELAY);
+    }
+
+    #[test]
+    fn seeks_to_typed_frame() {
+        let mut playback = playback(20);
+        for key in [
+            KeyCode::Char('g'),
+            KeyCode::Char('1'),
+            KeyCode::Char('9'),
+            KeyCode::Backspace,
+            KeyCode::Char('2'),
+        ] {
+            assert!(playback.handle(key));
+        }
+        // quitting is not possible while typing a frame
+        assert!(playback.handle(KeyCode::Char('q')));
+        assert_eq!(
+            playback.status(),
+            "seek to frame: 12_ (enter to jump, esc to cancel)"
+        );
+        playback.handle(KeyCode::Enter);
+        assert_eq!(playback.position, 12);
+        assert!(!playback.handle(KeyCode::Char('q')));
+    }
+
+    #[test]
+    fn seeking_ahead_waits_for_the_frame() {
+        let mut playback = playback(2);
+        playback.jump(4);
+        assert_eq!(playback.pending, Some(4));
+        assert!(!playback.is_playing());
+        playback.push(Frame::default());
+        playback.push(Frame::default());
+        assert_eq!(playback.position, 1);
+        playback.push(Frame::default());
+        assert_eq!(playback.position, 4);
+        assert_eq!(playback.pending, None);
+
+        playback.jump(10);
+        playback.finish();
+        assert_eq!(playback.position, 4);
+    }
+}
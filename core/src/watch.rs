@@ -0,0 +1,215 @@
+use std::{
+    fmt::Write as _,
+    fs,
+    io::{self, stdout, Write},
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crossterm::{cursor, execute, terminal};
+
+use crate::{
+    panic_message, solve_file,
+    theme::{self, Role, Theme},
+    DayRun, Year,
+};
+
+/// How often the input is checked for changes.
+const POLL: Duration = Duration::from_millis(250);
+
+/// What the file looked like when it was last solved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Stamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+fn stamp(path: &Path) -> Option<Stamp> {
+    let metadata = fs::metadata(path).ok()?;
+    Some(Stamp {
+        modified: metadata.modified().ok(),
+        len: metadata.len(),
+    })
+}
+
+/// Solves the day whenever the file at `path` changes, showing what changed since the last run.
+///
+/// Runs until interrupted. Inputs that cannot be read or make the solver panic are reported
+/// without stopping, since they are usually only half edited.
+pub fn watch(year: &Year, day: u8, path: &Path) -> io::Result<()> {
+    let mut previous: Option<DayRun> = None;
+    let mut seen = None;
+    loop {
+        let current = stamp(path);
+        if seen != Some(current) {
+            seen = Some(current);
+            let outcome = run(year, day, path);
+            execute!(
+                stdout(),
+                terminal::Clear(terminal::ClearType::All),
+                cursor::MoveTo(0, 0)
+            )?;
+            print!(
+                "{}",
+                render(theme::current(), day, path, previous.as_ref(), &outcome)
+            );
+            stdout().flush()?;
+            if let Ok(run) = outcome {
+                previous = Some(run);
+            }
+        }
+        thread::sleep(POLL);
+    }
+}
+
+fn run(year: &Year, day: u8, path: &Path) -> Result<DayRun, String> {
+    match panic::catch_unwind(AssertUnwindSafe(|| solve_file(year, day, path))) {
+        Ok(Ok(run)) => Ok(run),
+        Ok(Err(error)) => Err(format!("could not read {}: {error}", path.display())),
+        Err(payload) => Err(format!("solver panicked: {}", panic_message(&payload))),
+    }
+}
+
+fn render(
+    theme: &Theme,
+    day: u8,
+    path: &Path,
+    previous: Option<&DayRun>,
+    outcome: &Result<DayRun, String>,
+) -> String {
+    let mut out = String::new();
+    let header = format!("Day {day}, watching {}", path.display());
+    writeln!(out, "{}\n", theme.paint(Role::Header, header)).unwrap();
+
+    let run = match outcome {
+        Ok(run) => run,
+        Err(error) => {
+            writeln!(out, "{}", theme.paint(Role::Error, error)).unwrap();
+            return out;
+        }
+    };
+
+    for (part, answer) in run.answers.iter().enumerate() {
+        let label = format!("- Part {}:", part + 1);
+        writeln!(out, "{}", theme.paint(Role::Label, label)).unwrap();
+        let before = previous.map(|previous| &previous.answers[part]);
+        match before {
+            Some(before) if before != answer && answer.contains('\n') => {
+                writeln!(out, "{}", theme.paint(Role::Answer, answer.trim_end())).unwrap();
+                writeln!(out, "{}", theme.paint(Role::Separator, "was:")).unwrap();
+                writeln!(out, "{}", theme.paint(Role::Separator, before.trim_end())).unwrap();
+            }
+            Some(before) if before != answer => {
+                let was = format!("(was {before})");
+                writeln!(
+                    out,
+                    "{} {}",
+                    theme.paint(Role::Answer, answer),
+                    theme.paint(Role::Separator, was)
+                )
+                .unwrap();
+            }
+            _ => writeln!(out, "{}", theme.paint(Role::Answer, answer.trim_end())).unwrap(),
+        }
+    }
+
+    writeln!(out).unwrap();
+    let timings = &run.timings;
+    let before = previous.map(|previous| &previous.timings);
+    let phases = [
+        ("input", timings.input, before.map(|before| before.input)),
+        (
+            "part 1",
+            timings.parts[0],
+            before.map(|before| before.parts[0]),
+        ),
+        (
+            "part 2",
+            timings.parts[1],
+            before.map(|before| before.parts[1]),
+        ),
+    ];
+    for (name, time, before) in phases {
+        let mut line = format!("{name: <6} {time: >10.2?}");
+        if let Some(before) = before {
+            line += &format!(" ({})", change(before, time));
+        }
+        writeln!(out, "{}", theme.paint(Role::Timing, line)).unwrap();
+    }
+    out
+}
+
+fn change(before: Duration, after: Duration) -> String {
+    if after >= before {
+        format!("+{:.2?}", after - before)
+    } else {
+        format!("-{:.2?}", before - after)
+    }
+}
+
+#[cfg(test)]
+mod watch_tests {
+    use super::*;
+    use crate::Timings;
+
+    fn day_run(answers: [&str; 2], micros: [u64; 2]) -> DayRun {
+        DayRun {
+            day: 10,
+            answers: answers.map(String::from),
+            cached: [false; 2],
+            timings: Timings {
+                input: Duration::from_micros(1),
+                parts: micros.map(Duration::from_micros),
+            },
+            allocations: None,
+        }
+    }
+
+    #[test]
+    fn first_run_has_no_diff() {
+        let run = day_run(["13140", "##\n.#\n"], [10, 20]);
+        let rendered = render(
+            &Theme::MONOCHROME,
+            10,
+            Path::new("day_10.txt"),
+            None,
+            &Ok(run),
+        );
+        assert_eq!(
+            rendered,
+            "Day 10, watching day_10.txt\n\n- Part 1:\n13140\n- Part 2:\n##\n.#\n\n\
+             input      1.00µs\npart 1    10.00µs\npart 2    20.00µs\n"
+        );
+    }
+
+    #[test]
+    fn changes_are_shown_against_the_previous_run() {
+        let previous = day_run(["13140", "##\n.#\n"], [10, 20]);
+        let run = day_run(["13141", "##\n##\n"], [15, 5]);
+        let rendered = render(
+            &Theme::MONOCHROME,
+            10,
+            Path::new("day_10.txt"),
+            Some(&previous),
+            &Ok(run),
+        );
+        assert!(rendered.contains("13141 (was 13140)\n"));
+        assert!(rendered.contains("##\n##\nwas:\n##\n.#\n"));
+        assert!(rendered.contains("part 1    15.00µs (+5.00µs)\n"));
+        assert!(rendered.contains("part 2     5.00µs (-15.00µs)\n"));
+    }
+
+    #[test]
+    fn failures_are_reported() {
+        let rendered = render(
+            &Theme::MONOCHROME,
+            10,
+            Path::new("day_10.txt"),
+            None,
+            &Err(String::from("solver panicked: oops")),
+        );
+        assert!(rendered.ends_with("solver panicked: oops\n"));
+    }
+}
@@ -1,31 +1,49 @@
-use owo_colors::OwoColorize;
 use std::time::{Duration, Instant};
 
-use aoc::run_day;
+use aoc::{
+    cache, history, logging, run_day,
+    theme::{paint, Role},
+    YEAR,
+};
 
 fn main() {
+    // `cargo run --release --example all -- --no-cache` solves everything again
+    if std::env::args().any(|arg| arg == "--no-cache") {
+        cache::set_enabled(false);
+    }
+    logging::init(0);
     let mut timings = Vec::new();
+    let mut runs = Vec::new();
     for day in 1..=17 {
         let start = Instant::now();
-        run_day(day);
+        runs.push(run_day(day));
         timings.push(start.elapsed());
     }
+    // `cargo run -- history` shows how the timings change from run to run
+    if let Err(error) = history::record(&YEAR, runs) {
+        eprintln!("could not record the run in {}: {error}", YEAR.history);
+    }
     let mut total = Duration::default();
     for (day, time) in timings.into_iter().enumerate() {
         total += time;
         println!(
             "{}",
-            format!("Day {: >2} : {: >5} µs", day + 1, time.as_micros()).fg_rgb::<186, 187, 241>()
+            paint(
+                Role::Timing,
+                format!("Day {: >2} : {: >5} µs", day + 1, time.as_micros())
+            )
         );
     }
     println!();
     println!(
         "{}",
-        format!(
-            "Total time (including input and output):\n {} µs or {} ms",
-            total.as_micros(),
-            total.as_millis()
+        paint(
+            Role::Header,
+            format!(
+                "Total time (including input and output):\n {} µs or {} ms",
+                total.as_micros(),
+                total.as_millis()
+            )
         )
-        .fg_rgb::<140, 170, 238>()
     );
 }
@@ -0,0 +1,73 @@
+use std::{
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[allow(dead_code)]
+mod codes {
+    include!("src/codes.rs");
+}
+
+const PROTOTYPES: &str = r#"/* Caller owned memory that an answer is written to. */
+typedef struct aoc_buffer {
+    /* Where the answer is written, followed by a NUL. */
+    char *data;
+    /* Bytes available at data. */
+    size_t capacity;
+    /* Length of the answer without the NUL, set on success and when the buffer is too small. */
+    size_t len;
+} aoc_buffer;
+
+/* Solves one part of a day for the input of len bytes, which need not be NUL terminated. */
+int32_t aoc_solve(uint8_t day, uint8_t part, const char *input, size_t len, aoc_buffer *out);
+
+/* Like aoc_solve, also setting nanos to how long solving took unless it is NULL. */
+int32_t aoc_solve_timed(uint8_t day, uint8_t part, const char *input, size_t len,
+                        aoc_buffer *out, uint64_t *nanos);
+
+/* 1 if the day has a solver, 0 otherwise. */
+int32_t aoc_has_day(uint8_t day);
+
+/* What a code returned by aoc_solve means. Never NULL, and never to be freed. */
+const char *aoc_error_message(int32_t code);
+
+/* Version of the library. Never NULL, and never to be freed. */
+const char *aoc_version(void);
+"#;
+
+fn main() {
+    let mut header = String::from(concat!(
+        "/* Generated by ffi/build.rs, do not edit. */\n",
+        "#ifndef AOC_H\n#define AOC_H\n\n",
+        "#include <stddef.h>\n#include <stdint.h>\n\n",
+        "#ifdef __cplusplus\nextern \"C\" {\n#endif\n\n",
+    ));
+    for (name, value, description) in codes::CODES {
+        writeln!(header, "/*{description} */\n#define {name} {value}").unwrap();
+    }
+    header.push('\n');
+    header.push_str(PROTOTYPES);
+    header.push_str("\n#ifdef __cplusplus\n}\n#endif\n\n#endif /* AOC_H */\n");
+
+    // the header goes with the build, and only into the source tree when asked, so that a build
+    // never dirties the working tree
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("include/aoc.h");
+    write(&out, &header);
+    if env::var_os("AOC_UPDATE_HEADER").is_some() {
+        let manifest = env::var("CARGO_MANIFEST_DIR").unwrap();
+        write(&Path::new(&manifest).join("include/aoc.h"), &header);
+    }
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/codes.rs");
+    println!("cargo:rerun-if-env-changed=AOC_UPDATE_HEADER");
+}
+
+/// Writes the header only when it changed, so that its timestamp means something to make.
+fn write(path: &Path, header: &str) {
+    if fs::read_to_string(path).ok().as_deref() != Some(header) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, header).unwrap();
+    }
+}
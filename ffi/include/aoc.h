@@ -0,0 +1,57 @@
+/* Generated by ffi/build.rs, do not edit. */
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The answer was written to the buffer. */
+#define AOC_OK 0
+/* A required pointer was null. */
+#define AOC_ERR_NULL_POINTER 1
+/* There is no solver for the day. */
+#define AOC_ERR_NO_SUCH_DAY 2
+/* The part was neither 1 nor 2. */
+#define AOC_ERR_NO_SUCH_PART 3
+/* The input was not valid UTF-8. */
+#define AOC_ERR_INVALID_UTF8 4
+/* The buffer cannot hold the answer. Its length is set to the size needed. */
+#define AOC_ERR_BUFFER_TOO_SMALL 5
+/* The solver panicked, usually because the input is malformed. */
+#define AOC_ERR_PANIC 6
+
+/* Caller owned memory that an answer is written to. */
+typedef struct aoc_buffer {
+    /* Where the answer is written, followed by a NUL. */
+    char *data;
+    /* Bytes available at data. */
+    size_t capacity;
+    /* Length of the answer without the NUL, set on success and when the buffer is too small. */
+    size_t len;
+} aoc_buffer;
+
+/* Solves one part of a day for the input of len bytes, which need not be NUL terminated. */
+int32_t aoc_solve(uint8_t day, uint8_t part, const char *input, size_t len, aoc_buffer *out);
+
+/* Like aoc_solve, also setting nanos to how long solving took unless it is NULL. */
+int32_t aoc_solve_timed(uint8_t day, uint8_t part, const char *input, size_t len,
+                        aoc_buffer *out, uint64_t *nanos);
+
+/* 1 if the day has a solver, 0 otherwise. */
+int32_t aoc_has_day(uint8_t day);
+
+/* What a code returned by aoc_solve means. Never NULL, and never to be freed. */
+const char *aoc_error_message(int32_t code);
+
+/* Version of the library. Never NULL, and never to be freed. */
+const char *aoc_version(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* AOC_H */
@@ -0,0 +1,38 @@
+// Shared with build.rs, which writes these into the header, so keep to plain items.
+
+macro_rules! codes {
+    ($($(#[doc = $doc:literal])* $name:ident = $value:literal,)*) => {
+        $(
+            $(#[doc = $doc])*
+            pub const $name: i32 = $value;
+        )*
+
+        /// Every code as (name, value, description).
+        pub const CODES: &[(&str, i32, &str)] = &[$((stringify!($name), $value, concat!($($doc),*))),*];
+
+        /// The description of a code, NUL terminated.
+        pub fn description(code: i32) -> Option<&'static [u8]> {
+            match code {
+                $($value => Some(concat!($($doc),*, "\0").as_bytes()),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+codes! {
+    /// The answer was written to the buffer.
+    AOC_OK = 0,
+    /// A required pointer was null.
+    AOC_ERR_NULL_POINTER = 1,
+    /// There is no solver for the day.
+    AOC_ERR_NO_SUCH_DAY = 2,
+    /// The part was neither 1 nor 2.
+    AOC_ERR_NO_SUCH_PART = 3,
+    /// The input was not valid UTF-8.
+    AOC_ERR_INVALID_UTF8 = 4,
+    /// The buffer cannot hold the answer. Its length is set to the size needed.
+    AOC_ERR_BUFFER_TOO_SMALL = 5,
+    /// The solver panicked, usually because the input is malformed.
+    AOC_ERR_PANIC = 6,
+}
@@ -0,0 +1,161 @@
+use std::{
+    ffi::{c_char, CStr},
+    panic::{self, AssertUnwindSafe},
+    ptr, slice,
+};
+
+use aoc::{days, SolveError};
+
+mod codes;
+
+pub use codes::*;
+
+/// Caller owned memory that an answer is written to.
+#[repr(C)]
+pub struct AocBuffer {
+    pub data: *mut c_char,
+    pub capacity: usize,
+    pub len: usize,
+}
+
+/// Solves one part of a day, writing the answer and a NUL to `out`.
+///
+/// # Safety
+///
+/// `input` must point to `len` readable bytes, or may be null when `len` is 0. `out` must point
+/// to a buffer whose `data` has `capacity` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u8,
+    part: u8,
+    input: *const c_char,
+    len: usize,
+    out: *mut AocBuffer,
+) -> i32 {
+    aoc_solve_timed(day, part, input, len, out, ptr::null_mut())
+}
+
+/// Like [`aoc_solve`], also setting `nanos` to how long solving took unless it is null.
+///
+/// # Safety
+///
+/// As for [`aoc_solve`], and `nanos` must be null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve_timed(
+    day: u8,
+    part: u8,
+    input: *const c_char,
+    len: usize,
+    out: *mut AocBuffer,
+    nanos: *mut u64,
+) -> i32 {
+    let Some(out) = out.as_mut() else {
+        return AOC_ERR_NULL_POINTER;
+    };
+    if out.data.is_null() && out.capacity > 0 || input.is_null() && len > 0 {
+        return AOC_ERR_NULL_POINTER;
+    }
+    let bytes = if len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(input.cast::<u8>(), len)
+    };
+    let Ok(input) = std::str::from_utf8(bytes) else {
+        return AOC_ERR_INVALID_UTF8;
+    };
+
+    let solution = match panic::catch_unwind(AssertUnwindSafe(|| aoc::solve(day, part, input))) {
+        Ok(Ok(solution)) => solution,
+        Ok(Err(SolveError::NoSuchDay(_))) => return AOC_ERR_NO_SUCH_DAY,
+        Ok(Err(SolveError::NoSuchPart(_))) => return AOC_ERR_NO_SUCH_PART,
+        Err(_) => return AOC_ERR_PANIC,
+    };
+    if let Some(nanos) = nanos.as_mut() {
+        *nanos = solution.time.as_nanos() as u64;
+    }
+    let answer = solution.answer;
+
+    out.len = answer.len();
+    if answer.len() >= out.capacity {
+        return AOC_ERR_BUFFER_TOO_SMALL;
+    }
+    ptr::copy_nonoverlapping(answer.as_ptr(), out.data.cast::<u8>(), answer.len());
+    *out.data.add(answer.len()) = 0;
+    AOC_OK
+}
+
+/// 1 if the day has a solver, 0 otherwise.
+#[no_mangle]
+pub extern "C" fn aoc_has_day(day: u8) -> i32 {
+    days::get(day).is_some().into()
+}
+
+/// What a code returned by [`aoc_solve`] means.
+#[no_mangle]
+pub extern "C" fn aoc_error_message(code: i32) -> *const c_char {
+    let message = match codes::description(code) {
+        Some(description) => CStr::from_bytes_with_nul(description.trim_ascii_start()).unwrap(),
+        None => c"Unknown error code.",
+    };
+    message.as_ptr()
+}
+
+#[no_mangle]
+pub extern "C" fn aoc_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+#[cfg(test)]
+mod ffi_tests {
+    use super::*;
+
+    fn solve(day: u8, part: u8, input: &str, capacity: usize) -> (i32, String, usize) {
+        let mut data = vec![0 as c_char; capacity];
+        let mut out = AocBuffer {
+            data: data.as_mut_ptr(),
+            capacity,
+            len: 0,
+        };
+        let code = unsafe { aoc_solve(day, part, input.as_ptr().cast(), input.len(), &mut out) };
+        let answer = if code == AOC_OK {
+            unsafe { CStr::from_ptr(out.data) }
+                .to_string_lossy()
+                .into_owned()
+        } else {
+            String::new()
+        };
+        (code, answer, out.len)
+    }
+
+    #[test]
+    fn solves_into_the_buffer() {
+        let input = "1000\n2000\n\n4000\n";
+        assert_eq!(solve(1, 1, input, 16), (AOC_OK, String::from("4000"), 4));
+        // the NUL needs room as well
+        assert_eq!(
+            solve(1, 1, input, 4),
+            (AOC_ERR_BUFFER_TOO_SMALL, String::new(), 4)
+        );
+    }
+
+    #[test]
+    fn reports_errors() {
+        assert_eq!(solve(26, 1, "", 16).0, AOC_ERR_NO_SUCH_DAY);
+        assert_eq!(solve(1, 3, "", 16).0, AOC_ERR_NO_SUCH_PART);
+        assert_eq!(solve(20, 1, "not a number", 16).0, AOC_ERR_PANIC);
+        let code = unsafe { aoc_solve(1, 1, ptr::null(), 0, ptr::null_mut()) };
+        assert_eq!(code, AOC_ERR_NULL_POINTER);
+        assert_eq!(aoc_has_day(25), 1);
+        assert_eq!(aoc_has_day(0), 0);
+        let message = unsafe { CStr::from_ptr(aoc_error_message(AOC_ERR_NO_SUCH_DAY)) };
+        assert_eq!(message.to_str(), Ok("There is no solver for the day."));
+    }
+
+    #[test]
+    fn every_code_has_a_message() {
+        for (name, code, _) in CODES {
+            let message = unsafe { CStr::from_ptr(aoc_error_message(*code)) };
+            assert_ne!(message.to_str().unwrap(), "Unknown error code.", "{name}");
+        }
+    }
+}
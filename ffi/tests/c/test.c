@@ -0,0 +1,50 @@
+/* Links against the cdylib and solves the examples through the C interface. */
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc.h"
+
+static int failures = 0;
+
+static void check(int condition, const char *what) {
+    if (!condition) {
+        fprintf(stderr, "FAILED: %s\n", what);
+        failures++;
+    }
+}
+
+int main(void) {
+    const char *calories = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+    char data[64];
+    aoc_buffer out = {data, sizeof data, 0};
+    uint64_t nanos = 0;
+
+    check(aoc_solve(1, 1, calories, strlen(calories), &out) == AOC_OK, "day 1 part 1 solves");
+    check(strcmp(out.data, "24000") == 0, "day 1 part 1 answer");
+    check(out.len == 5, "day 1 part 1 length");
+
+    check(aoc_solve_timed(1, 2, calories, strlen(calories), &out, &nanos) == AOC_OK,
+          "day 1 part 2 solves");
+    check(strcmp(out.data, "45000") == 0, "day 1 part 2 answer");
+
+    char tiny[3];
+    aoc_buffer small = {tiny, sizeof tiny, 0};
+    check(aoc_solve(1, 1, calories, strlen(calories), &small) == AOC_ERR_BUFFER_TOO_SMALL,
+          "small buffers are refused");
+    check(small.len == 5, "small buffers learn the length needed");
+
+    check(aoc_solve(26, 1, calories, strlen(calories), &out) == AOC_ERR_NO_SUCH_DAY,
+          "unknown days are refused");
+    check(aoc_solve(1, 3, calories, strlen(calories), &out) == AOC_ERR_NO_SUCH_PART,
+          "unknown parts are refused");
+    check(aoc_solve(1, 1, NULL, 4, &out) == AOC_ERR_NULL_POINTER, "null input is refused");
+    check(aoc_solve(20, 1, "x", 1, &out) == AOC_ERR_PANIC, "panics are caught");
+    check(aoc_has_day(25) == 1 && aoc_has_day(26) == 0, "days are listed");
+    check(strlen(aoc_error_message(AOC_ERR_PANIC)) > 0, "codes have messages");
+    check(strlen(aoc_version()) > 0, "version is set");
+
+    if (failures == 0) {
+        printf("ok\n");
+    }
+    return failures != 0;
+}
@@ -0,0 +1,49 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// Builds `tests/c/test.c` against the cdylib and runs it.
+#[test]
+fn c_program_links_and_solves() {
+    let manifest = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // tests run from target/<profile>/deps, which is where cargo builds the cdylib for them
+    let exe = env::current_exe().unwrap();
+    let libraries = exe.parent().unwrap();
+    let program = libraries.join("aoc_ffi_c_test");
+    let compiler = env::var("CC").unwrap_or_else(|_| String::from("cc"));
+
+    let compiled = Command::new(&compiler)
+        .arg(manifest.join("tests/c/test.c"))
+        .arg("-I")
+        .arg(Path::new(env!("OUT_DIR")).join("include"))
+        .arg("-L")
+        .arg(libraries)
+        .arg(format!("-Wl,-rpath,{}", libraries.display()))
+        .arg("-laoc_ffi")
+        .arg("-o")
+        .arg(&program)
+        .status();
+    let Ok(compiled) = compiled else {
+        eprintln!("skipping, there is no C compiler at `{compiler}`");
+        return;
+    };
+    assert!(compiled.success());
+
+    let output = Command::new(&program).output().unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{stderr}");
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "ok\n");
+}
+
+/// The header checked in at `include/aoc.h` is the one the build generates.
+#[test]
+fn checked_in_header_is_current() {
+    let generated = Path::new(env!("OUT_DIR")).join("include/aoc.h");
+    let checked_in = Path::new(env!("CARGO_MANIFEST_DIR")).join("include/aoc.h");
+    assert!(
+        fs::read_to_string(generated).unwrap() == fs::read_to_string(checked_in).unwrap(),
+        "include/aoc.h is out of date, update it with `AOC_UPDATE_HEADER=1 cargo build -p aoc-ffi`"
+    );
+}
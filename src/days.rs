@@ -3,27 +3,27 @@ pub mod day_02;
 pub mod day_03;
 pub mod day_04;
 pub mod day_05;
-mod day_06;
-mod day_07;
-mod day_08;
-mod day_09;
-mod day_10;
-mod day_11;
-mod day_12;
-mod day_13;
-mod day_14;
+pub mod day_06;
+pub mod day_07;
+pub mod day_08;
+pub mod day_09;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_13;
+pub mod day_14;
 pub mod day_15;
-mod day_16;
+pub mod day_16;
 mod day_16b;
-mod day_17;
-mod day_18;
-mod day_19;
-mod day_20;
-mod day_21;
-mod day_22;
-mod day_23;
-mod day_24;
-mod day_25;
+pub mod day_17;
+pub mod day_18;
+pub mod day_19;
+pub mod day_20;
+pub mod day_21;
+pub mod day_22;
+pub mod day_23;
+pub mod day_24;
+pub mod day_25;
 
 pub use day_01::Day01;
 pub use day_02::Day02;
@@ -51,7 +51,64 @@ pub use day_23::Day23;
 pub use day_24::Day24;
 pub use day_25::Day25;
 
-pub trait Day {
-    fn part_1(&self, input: &str) -> String;
-    fn part_2(&self, input: &str) -> String;
+pub use aoc_core::Day;
+
+/// Where the days' tests keep the renderings they compare against.
+#[cfg(test)]
+const SNAPSHOTS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/days/snapshots");
+
+/// Looks up the solver for a day.
+pub fn get(day: u8) -> Option<&'static dyn Day> {
+    let day: &'static dyn Day = match day {
+        1 => &Day01,
+        2 => &Day02,
+        3 => &Day03,
+        4 => &Day04,
+        5 => &Day05,
+        6 => &Day06,
+        7 => &Day07,
+        8 => &Day08,
+        9 => &Day09,
+        10 => &Day10,
+        11 => &Day11,
+        12 => &Day12,
+        13 => &Day13,
+        14 => &Day14,
+        15 => &Day15,
+        16 => &Day16,
+        17 => &Day17,
+        18 => &Day18,
+        19 => &Day19,
+        20 => &Day20,
+        21 => &Day21,
+        22 => &Day22,
+        23 => &Day23,
+        24 => &Day24,
+        25 => &Day25,
+        _ => return None,
+    };
+    Some(day)
+}
+
+#[cfg(test)]
+mod days_tests {
+    use super::*;
+    use aoc_core::generate::Rng;
+
+    #[test]
+    fn generated_inputs_are_well_formed() {
+        for day in 1..=25 {
+            let solver = get(day).unwrap();
+            for seed in 0..20 {
+                let Some(input) = solver.generate(&mut Rng::new(seed)) else {
+                    break;
+                };
+                if let Some(problems) = solver.lint(&input) {
+                    assert_eq!(problems, vec![], "day {day}, seed {seed}");
+                }
+                solver.part_1(&input);
+                solver.part_2(&input);
+            }
+        }
+    }
 }
@@ -1,3 +1,5 @@
+use std::io::{self, BufRead};
+
 use nom::{
     bytes::complete::take_while,
     character::complete::newline,
@@ -8,6 +10,9 @@ use nom::{
 };
 
 use super::Day;
+use crate::generate::Rng;
+use crate::lint::{self, Problem};
+use crate::stream::{self, Stream};
 
 pub struct Day01;
 
@@ -38,6 +43,72 @@ impl Day for Day01 {
         .sum::<u32>()
         .to_string()
     }
+
+    fn streaming(&self) -> Option<&dyn Stream> {
+        Some(self)
+    }
+
+    fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+        let elves = lint::blocks(input).into_iter();
+        let problems = elves.flat_map(|(first, elf)| {
+            lint::lines(first, elf, |line| lint::scan(line, "{n}").map(drop))
+        });
+        Some(problems.collect())
+    }
+
+    fn generate(&self, rng: &mut Rng) -> Option<String> {
+        let elves: Vec<String> = (0..rng.range(3..=200))
+            .map(|_| {
+                let snacks = rng.range(1..=10);
+                (0..snacks)
+                    .map(|_| format!("{}\n", rng.range(1_000..=60_000)))
+                    .collect()
+            })
+            .collect();
+        Some(elves.join("\n"))
+    }
+}
+
+impl Stream for Day01 {
+    fn stream_part_1(&self, input: &mut dyn BufRead) -> io::Result<String> {
+        Ok(fold_elves(input, 0, u32::max)?.to_string())
+    }
+
+    fn stream_part_2(&self, input: &mut dyn BufRead) -> io::Result<String> {
+        let top = fold_elves(input, [0; 3], |mut top, elf| {
+            let index = get_min_index(top);
+            top[index] = top[index].max(elf);
+            top
+        })?;
+        Ok(top.into_iter().sum::<u32>().to_string())
+    }
+}
+
+/// Folds the calories each elf carries, one elf at a time.
+fn fold_elves<T>(
+    input: &mut dyn BufRead,
+    init: T,
+    mut fold: impl FnMut(T, u32) -> T,
+) -> io::Result<T> {
+    let mut acc = Some(init);
+    let mut elf = None;
+    stream::for_each_line(input, |line| {
+        if line.is_empty() {
+            if let Some(calories) = elf.take() {
+                acc = acc.take().map(|acc| fold(acc, calories));
+            }
+            return Ok(());
+        }
+        let food: u32 = lexical::parse(line)
+            .map_err(|_| stream::invalid(format!("`{line}` is not a number")))?;
+        elf = Some(elf.unwrap_or(0) + food);
+        Ok(())
+    })?;
+    let acc = acc.unwrap();
+    Ok(match elf {
+        Some(calories) => fold(acc, calories),
+        None => acc,
+    })
 }
 
 fn get_min_index(top: [u32; 3]) -> usize {
@@ -123,4 +194,17 @@ mod day_01_tests {
         let (_, elves) = parse_elves(input).unwrap();
         assert_eq!(elves, vec![6000, 6000]);
     }
+
+    #[test]
+    fn streams_elves() {
+        let input = "1000\n2000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+        let stream = |part| {
+            stream::solve(&Day01, part, &mut input.as_bytes())
+                .unwrap()
+                .answer
+        };
+        assert_eq!(stream(1), Day01.part_1(input));
+        assert_eq!(stream(2), Day01.part_2(input));
+        assert_eq!(stream(2), "45000");
+    }
 }
@@ -1,4 +1,5 @@
 use super::Day;
+use crate::lint::{self, Problem};
 
 pub struct Day02;
 
@@ -46,6 +47,15 @@ impl Day for Day02 {
             .sum::<usize>()
             .to_string()
     }
+
+    fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+        Some(lint::lines(1, input, |line| match line.as_bytes() {
+            [b'A'..=b'C', b' ', b'X'..=b'Z'] => Ok(()),
+            _ => Err(format!(
+                "expected `A`-`C`, a space and `X`-`Z`, found `{line}`"
+            )),
+        }))
+    }
 }
 
 pub fn run(input: &str) -> (u32, u32) {
@@ -1,4 +1,5 @@
 use super::Day;
+use crate::lint::{self, Problem};
 
 pub struct Day03;
 
@@ -30,6 +31,26 @@ impl Day for Day03 {
             .sum::<usize>()
             .to_string()
     }
+
+    fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+        let mut problems = lint::lines(1, input, |bag| {
+            if let Some(item) = bag.chars().find(|item| !item.is_ascii_alphabetic()) {
+                Err(format!("unexpected {item:?}, expected letters"))
+            } else if bag.is_empty() || !bag.len().is_multiple_of(2) {
+                Err(format!(
+                    "{} items cannot be split into two compartments",
+                    bag.len()
+                ))
+            } else {
+                Ok(())
+            }
+        });
+        let bags = input.lines().count();
+        if !bags.is_multiple_of(3) {
+            problems.push(Problem::new(bags, "the elves are not in groups of three"));
+        }
+        Some(problems)
+    }
 }
 
 fn priority(c: char) -> usize {
@@ -72,4 +93,4 @@ pub fn part_2(input: &[&str]) -> usize {
 pub fn run(input: &str) -> (usize, usize) {
     let parsed = parse_input(input);
     (part_1(&parsed), part_2(&parsed))
-}
\ No newline at end of file
+}
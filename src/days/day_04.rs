@@ -6,6 +6,7 @@ use nom::{
 };
 
 use super::Day;
+use crate::lint::{self, Problem};
 
 pub struct Day04;
 
@@ -35,6 +36,12 @@ impl Day for Day04 {
             .count()
             .to_string()
     }
+
+    fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+        Some(lint::lines(1, input, |line| {
+            lint::scan(line, "{n}-{n},{n}-{n}").map(drop)
+        }))
+    }
 }
 
 fn to_pair(input: &str) -> (usize, usize) {
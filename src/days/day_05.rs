@@ -8,6 +8,7 @@ use nom::{
 };
 
 use super::Day;
+use crate::lint::{self, Problem};
 
 pub struct Day05;
 
@@ -36,6 +37,49 @@ impl Day for Day05 {
         }
         cmap.iter().map(|v| v.last().unwrap()).collect()
     }
+
+    fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+        let blocks = lint::blocks(input);
+        let (crates, instructions) = match blocks[..] {
+            [(_, crates), instructions] => (crates, Some(instructions)),
+            _ => (input, None),
+        };
+        let stacks = crates.lines().count();
+        let mut problems = lint::lines(1, crates, |line| {
+            let slots = line.as_bytes().chunks(4);
+            for (stack, slot) in slots.enumerate() {
+                match slot {
+                    [b'[', b'A'..=b'Z', b']', ..] | [b' ', b'0'..=b'9', b' ', ..] => {}
+                    [b' ', b' ', b' ', ..] => {}
+                    _ => return Err(format!("stack {} should be `[X]` or blank", stack + 1)),
+                }
+                if slot.len() == 4 && slot[3] != b' ' {
+                    return Err(format!("stack {} should be followed by a space", stack + 1));
+                }
+            }
+            Ok(())
+        });
+        if let Some(numbers) = crates.lines().last() {
+            if !numbers
+                .split_whitespace()
+                .all(|n| n.parse::<usize>().is_ok())
+            {
+                problems.push(Problem::new(stacks, "expected the stack numbers"));
+            }
+        }
+        match instructions {
+            Some((first, instructions)) => {
+                problems.extend(lint::lines(first, instructions, |line| {
+                    lint::scan(line, "move {n} from {n} to {n}").map(drop)
+                }))
+            }
+            None => {
+                let message = "expected the crates and the instructions, separated by a blank line";
+                problems.push(Problem::new(1, message));
+            }
+        }
+        Some(problems)
+    }
 }
 
 fn get_crates(input: &str) -> Vec<Vec<char>> {
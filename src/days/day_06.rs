@@ -1,6 +1,12 @@
-use std::ops::Add;
+use std::{
+    io::{self, BufRead, Read},
+    ops::Add,
+};
 
 use super::Day;
+use crate::generate::Rng;
+use crate::lint::{self, Problem};
+use crate::stream::{self, Stream};
 
 pub struct Day06;
 
@@ -12,6 +18,26 @@ impl Day for Day06 {
     fn part_2(&self, input: &str) -> String {
         find::<14>(input)
     }
+
+    fn streaming(&self) -> Option<&dyn Stream> {
+        Some(self)
+    }
+
+    fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+        Some(lint::single_line(1, input, "abcdefghijklmnopqrstuvwxyz"))
+    }
+
+    fn generate(&self, rng: &mut Rng) -> Option<String> {
+        let letters: Vec<char> = ('a'..='z').collect();
+        // fewer letters make markers rarer
+        let letters = &letters[..rng.range(3..=26) as usize];
+        let mut signal: String = (0..rng.range(0..=4_000))
+            .map(|_| *rng.pick(letters))
+            .collect();
+        // so that there is always a marker, however unlucky the rest is
+        signal.push_str("abcdefghijklmn\n");
+        Some(signal)
+    }
 }
 
 #[allow(clippy::search_is_some)]
@@ -32,3 +58,32 @@ fn find<const W: usize>(input: &str) -> String {
         .add(W)
         .to_string()
 }
+
+impl Stream for Day06 {
+    fn stream_part_1(&self, input: &mut dyn BufRead) -> io::Result<String> {
+        find_in::<4>(input)
+    }
+
+    fn stream_part_2(&self, input: &mut dyn BufRead) -> io::Result<String> {
+        find_in::<14>(input)
+    }
+}
+
+/// Like `find`, but only ever holding the last `W` characters.
+fn find_in<const W: usize>(input: &mut dyn BufRead) -> io::Result<String> {
+    let mut window = [0; W];
+    for (read, byte) in input.bytes().enumerate() {
+        let byte = byte?;
+        if byte == b'\n' {
+            break;
+        }
+        window[read % W] = byte;
+        let distinct = (0..W).all(|i| !window[i + 1..].contains(&window[i]));
+        if read + 1 >= W && distinct {
+            return Ok((read + 1).to_string());
+        }
+    }
+    Err(stream::invalid(format!(
+        "no {W} different characters in a row"
+    )))
+}
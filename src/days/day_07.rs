@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 
 use super::Day;
+use crate::lint::{self, Problem};
 
 type Dirs = HashMap<u64, Vec<u64>>;
 type Files = HashMap<u64, Vec<u64>>;
@@ -12,15 +13,9 @@ pub struct Day07;
 impl Day for Day07 {
     fn part_1(&self, input: &str) -> String {
         let (dirs, files) = parse(input);
-        dirs.keys()
-            .filter_map(|con| {
-                let size = size(*con, &dirs, &files);
-                if size <= 100_000 {
-                    Some(size)
-                } else {
-                    None
-                }
-            })
+        sizes(&dirs, &files)
+            .into_values()
+            .filter(|&size| size <= 100_000)
             .sum::<u64>()
             .to_string()
     }
@@ -29,25 +24,34 @@ impl Day for Day07 {
         let (dirs, files) = parse(input);
         let total_size: u64 = files.values().flatten().sum();
         let remaining = 30_000_000 - (70_000_000 - total_size);
-        dirs.keys()
-            .filter_map(|con| {
-                let size = size(*con, &dirs, &files);
-                if size >= remaining {
-                    Some(size)
-                } else {
-                    None
-                }
-            })
+        sizes(&dirs, &files)
+            .into_values()
+            .filter(|&size| size >= remaining)
             .min()
             .unwrap()
             .to_string()
     }
+
+    fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+        Some(lint::lines(1, input, |line| {
+            let words: Vec<&str> = line.split(' ').collect();
+            match words[..] {
+                ["$", "ls"] | ["$", "cd", _] | ["dir", _] => Ok(()),
+                [size, _] if size.parse::<u64>().is_ok() => Ok(()),
+                _ => Err(format!(
+                    "expected `$ cd`, `$ ls`, `dir` or a file size, found `{line}`"
+                )),
+            }
+        }))
+    }
 }
 
 fn parse(input: &str) -> (Dirs, Files) {
     let mut dirs: Dirs = HashMap::new();
     let mut files: Files = HashMap::new();
-    let mut dir_stack: Vec<&str> = vec!["/"];
+    // each directory is named by hashing its name into its parent's, so that going deeper costs
+    // the same however deep it already is
+    let mut dir_stack: Vec<u64> = vec![child(0, "/")];
 
     let mut context: u64 = 0;
     for line in input.lines() {
@@ -60,24 +64,15 @@ fn parse(input: &str) -> (Dirs, Files) {
                     ".." => {
                         dir_stack.pop();
                     }
-                    dir => dir_stack.push(dir),
+                    dir => dir_stack.push(child(*dir_stack.last().unwrap(), dir)),
                 },
-                "ls" => {
-                    let mut hasher = DefaultHasher::new();
-                    dir_stack.hash(&mut hasher);
-                    context = hasher.finish();
-                }
+                "ls" => context = *dir_stack.last().unwrap(),
                 _ => unreachable!(),
             },
             // ls output
             item => match item {
                 "dir" => {
-                    let dir = line.next().unwrap();
-                    let mut hasher = DefaultHasher::new();
-                    let mut new = dir_stack.clone();
-                    new.push(dir);
-                    new.hash(&mut hasher);
-                    let hash = hasher.finish();
+                    let hash = child(context, line.next().unwrap());
                     dirs.entry(context).or_default().push(hash);
                     dirs.insert(hash, Vec::new());
                 }
@@ -91,12 +86,57 @@ fn parse(input: &str) -> (Dirs, Files) {
     (dirs, files)
 }
 
-fn size(context: u64, dirs: &Dirs, files: &Files) -> u64 {
-    files.get(&context).map_or(0, |v| v.iter().sum::<u64>())
-        + dirs
-            .get(&context)
-            .unwrap()
-            .iter()
-            .map(|new_context| size(*new_context, dirs, files))
-            .sum::<u64>()
+fn child(parent: u64, name: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    parent.hash(&mut hasher);
+    name.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The total size of every directory, each summed after its children with an explicit stack, as
+/// the tree can be far deeper than the call stack.
+fn sizes(dirs: &Dirs, files: &Files) -> HashMap<u64, u64> {
+    let mut sizes = HashMap::with_capacity(dirs.len());
+    for &start in dirs.keys() {
+        let mut stack = vec![(start, false)];
+        while let Some((dir, expanded)) = stack.pop() {
+            if sizes.contains_key(&dir) {
+                continue;
+            }
+            let children = &dirs[&dir];
+            if expanded {
+                let size = files.get(&dir).map_or(0, |v| v.iter().sum::<u64>())
+                    + children.iter().map(|child| sizes[child]).sum::<u64>();
+                sizes.insert(dir, size);
+            } else {
+                stack.push((dir, true));
+                stack.extend(children.iter().map(|&child| (child, false)));
+            }
+        }
+    }
+    sizes
+}
+
+#[cfg(test)]
+mod day_07_tests {
+    use super::*;
+
+    #[test]
+    fn sizes_deep_trees() {
+        let depth = 1_000_000;
+        let mut input = String::from("$ cd /\n");
+        for _ in 0..depth {
+            input.push_str("$ ls\ndir a\n1 f\n$ cd a\n");
+        }
+        input.push_str("$ ls\n5 g\n");
+        let (dirs, files) = parse(&input);
+        let sizes = sizes(&dirs, &files);
+        assert_eq!(sizes.len(), depth + 1);
+        assert_eq!(sizes.values().max(), Some(&(depth as u64 + 5)));
+        // every directory holds one more byte than the one inside it, from 5 at the bottom
+        assert_eq!(
+            Day07.part_1(&input),
+            (5..=100_000u64).sum::<u64>().to_string()
+        );
+    }
 }
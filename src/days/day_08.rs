@@ -1,6 +1,7 @@
 use std::ops::Add;
 
 use super::Day;
+use crate::lint::{self, Problem};
 
 pub struct Day08;
 
@@ -62,6 +63,10 @@ impl Day for Day08 {
             .unwrap()
             .to_string()
     }
+
+    fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+        Some(lint::grid(1, input, "0123456789"))
+    }
 }
 
 fn parse(input: &str) -> Vec<usize> {
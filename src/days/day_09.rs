@@ -1,6 +1,13 @@
-use std::collections::HashSet;
+use std::{
+    collections::HashSet,
+    io::{self, BufRead},
+};
 
 use super::Day;
+use crate::generate::Rng;
+use crate::lint::{self, Problem};
+use crate::stream::{self, Stream};
+use crate::visualize::{Frame, FrameSink, Highlight, NoFrames, Visualize};
 
 /// Position
 type Position = (isize, isize);
@@ -39,56 +46,161 @@ impl Day for Day09 {
     }
 
     fn part_2(&self, input: &str) -> String {
-        let mut knots = [(0, 0); 10];
-        let mut positions: Positions = HashSet::from_iter(vec![(0, 0)]);
-        for step in input.lines() {
-            let (dir, times) = step.split_once(char::is_whitespace).unwrap();
-            match dir {
-                "R" => {
-                    for _ in 0..times.parse().unwrap() {
-                        let head = &mut knots[0];
-                        head.0 += 1;
-
-                        if update_knots::<10>(&mut knots) {
-                            positions.insert(knots[9]);
-                        }
-                    }
-                }
-                "L" => {
-                    for _ in 0..times.parse().unwrap() {
-                        let head = &mut knots[0];
-                        head.0 -= 1;
-
-                        if update_knots::<10>(&mut knots) {
-                            positions.insert(knots[9]);
-                        }
-                    }
-                }
-                "U" => {
-                    for _ in 0..times.parse().unwrap() {
-                        let head = &mut knots[0];
-                        head.1 += 1;
-
-                        if update_knots::<10>(&mut knots) {
-                            positions.insert(knots[9]);
-                        }
-                    }
-                }
-                "D" => {
-                    for _ in 0..times.parse().unwrap() {
-                        let head = &mut knots[0];
-                        head.1 -= 1;
-
-                        if update_knots::<10>(&mut knots) {
-                            positions.insert(knots[9]);
-                        }
-                    }
-                }
-                _ => (),
-            };
+        simulate::<10, _>(input, &mut NoFrames).to_string()
+    }
+
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        Some(self)
+    }
+
+    fn streaming(&self) -> Option<&dyn Stream> {
+        Some(self)
+    }
+
+    fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+        Some(lint::lines(1, input, |step| {
+            let fields = lint::scan(step, "{w} {n}")?;
+            match fields[0] {
+                "U" | "D" | "L" | "R" => Ok(()),
+                _ => Err(format!("unknown direction `{}`", fields[0])),
+            }
+        }))
+    }
+
+    fn generate(&self, rng: &mut Rng) -> Option<String> {
+        let steps = (0..rng.range(1..=2_000)).map(|_| {
+            let direction = rng.pick(&["U", "D", "L", "R"]);
+            format!("{direction} {}\n", rng.range(1..=20))
+        });
+        Some(steps.collect())
+    }
+}
+
+impl Stream for Day09 {
+    fn stream_part_1(&self, input: &mut dyn BufRead) -> io::Result<String> {
+        stream_moves::<2>(input)
+    }
+
+    fn stream_part_2(&self, input: &mut dyn BufRead) -> io::Result<String> {
+        stream_moves::<10>(input)
+    }
+}
+
+impl Visualize for Day09 {
+    fn visualize(&self, input: &str, sink: &mut dyn FrameSink) {
+        simulate::<10, _>(input, sink);
+    }
+}
+
+/// Moves the head one step at a time, returning the number of positions the tail visited.
+fn simulate<const K: usize, S: FrameSink + ?Sized>(input: &str, sink: &mut S) -> usize {
+    let mut rope = Rope::<K>::new();
+    for step in input.lines() {
+        if let Err(error) = rope.step(step, sink) {
+            panic!("{error}");
         }
-        positions.len().to_string()
     }
+    rope.positions.len()
+}
+
+/// The knots, and every position the tail has visited.
+struct Rope<const K: usize> {
+    knots: [Position; K],
+    positions: Positions,
+}
+
+impl<const K: usize> Rope<K> {
+    fn new() -> Self {
+        Self {
+            knots: [(0, 0); K],
+            positions: HashSet::from_iter(vec![(0, 0)]),
+        }
+    }
+
+    fn step<S: FrameSink + ?Sized>(&mut self, step: &str, sink: &mut S) -> Result<(), String> {
+        let ((dx, dy), times) = parse_step(step)?;
+        for _ in 0..times {
+            let head = &mut self.knots[0];
+            head.0 += dx;
+            head.1 += dy;
+
+            if update_knots::<K>(&mut self.knots) {
+                self.positions.insert(self.knots[K - 1]);
+            }
+            if sink.is_recording() {
+                sink.emit(rope_frame(&self.knots, &self.positions, step));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Which way the step moves the head and how far, or why it is not a step.
+fn parse_step(step: &str) -> Result<(Position, usize), String> {
+    let (dir, times) = step
+        .split_once(char::is_whitespace)
+        .ok_or_else(|| format!("`{step}` is not a step"))?;
+    let direction = match dir {
+        "R" => (1, 0),
+        "L" => (-1, 0),
+        "U" => (0, 1),
+        "D" => (0, -1),
+        _ => return Err(format!("unknown direction `{dir}`")),
+    };
+    let times = times
+        .parse()
+        .map_err(|_| format!("`{times}` is not a number of steps"))?;
+    Ok((direction, times))
+}
+
+/// Streams the moves, returning the number of positions the tail visited.
+fn stream_moves<const K: usize>(input: &mut dyn BufRead) -> io::Result<String> {
+    let mut rope = Rope::<K>::new();
+    stream::for_each_line(input, |step| {
+        rope.step(step, &mut NoFrames).map_err(stream::invalid)
+    })?;
+    Ok(rope.positions.len().to_string())
+}
+
+/// Frames a window around the head, with y increasing upwards.
+fn rope_frame(knots: &[Position], positions: &Positions, step: &str) -> Frame {
+    const HALF_WIDTH: isize = 30;
+    const HALF_HEIGHT: isize = 12;
+
+    let (head_x, head_y) = knots[0];
+    let left = head_x - HALF_WIDTH;
+    let top = head_y + HALF_HEIGHT;
+    let to_point = |(x, y): Position| {
+        let col = x - left;
+        let row = top - y;
+        ((0..=2 * HALF_WIDTH).contains(&col) && (0..=2 * HALF_HEIGHT).contains(&row))
+            .then_some((col as usize, row as usize))
+    };
+
+    let mut frame = Frame::new(vec![
+        vec!['.'; 2 * HALF_WIDTH as usize + 1];
+        2 * HALF_HEIGHT as usize + 1
+    ])
+    .with_caption(format!("{step} | tail visited {}", positions.len()));
+    for &position in positions {
+        if let Some(point) = to_point(position) {
+            frame.overlay(point, '#', Highlight::Muted);
+        }
+    }
+    if let Some(point) = to_point((0, 0)) {
+        frame.overlay(point, 's', Highlight::Muted);
+    }
+    for (n, &knot) in knots.iter().enumerate().rev() {
+        if let Some(point) = to_point(knot) {
+            if n == 0 {
+                frame.overlay(point, 'H', Highlight::Primary);
+            } else {
+                let glyph = char::from_digit(n as u32, 36).unwrap_or('T');
+                frame.overlay(point, glyph, Highlight::Secondary);
+            }
+        }
+    }
+    frame
 }
 
 fn move_knot(head: Position, tail: &mut Position) -> bool {
@@ -116,3 +228,20 @@ fn update_knots<const K: usize>(knots: &mut [Position; K]) -> bool {
     }
     moved
 }
+
+#[cfg(test)]
+mod day_09_tests {
+    use super::*;
+
+    #[test]
+    fn streams_moves() {
+        let input = "R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2\n";
+        let stream = |part, input: &str| stream::solve(&Day09, part, &mut input.as_bytes());
+        assert_eq!(stream(1, input).unwrap().answer, Day09.part_1(input));
+        assert_eq!(stream(2, input).unwrap().answer, "1");
+        for bad in ["R 4\n\nU 4\n", "R 4\nU four\n", "X 1\n"] {
+            let error = stream(1, bad).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        }
+    }
+}
@@ -1,4 +1,9 @@
+use std::io::{self, BufRead};
+
 use super::Day;
+use crate::generate::Rng;
+use crate::lint::{self, Problem};
+use crate::stream::{self, Stream};
 
 #[derive(Debug)]
 enum Op {
@@ -11,75 +16,184 @@ enum State {
     Adding(isize),
 }
 
+/// Why there is no answer when the ops run out before the last cycle.
+const TOO_SHORT: &str = "the program ends too soon";
+
 pub struct Day10;
 
 impl Day for Day10 {
     fn part_1(&self, input: &str) -> String {
-        let mut ops = parse_to_iter(input);
+        signal_strength(&mut parse_to_iter(input)).expect(TOO_SHORT)
+    }
+
+    fn part_2(&self, input: &str) -> String {
+        draw(&mut parse_to_iter(input)).expect(TOO_SHORT)
+    }
 
-        let mut x = 1;
-        let mut state = State::Idle;
+    fn streaming(&self) -> Option<&dyn Stream> {
+        Some(self)
+    }
+
+    fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+        Some(lint::lines(1, input, |line| match line {
+            "noop" => Ok(()),
+            _ => lint::scan(line, "addx {i}").map(drop),
+        }))
+    }
 
-        let mut sum = 0;
-        let mut check = |cycle: isize, x: isize| {
-            if (cycle - 20) % 40 == 0 {
-                sum += cycle * x;
+    fn generate(&self, rng: &mut Rng) -> Option<String> {
+        // the screen needs 240 cycles of instructions
+        let mut program = String::new();
+        let mut cycles = 0;
+        while cycles < 240 {
+            if rng.range(0..=2) == 0 {
+                program.push_str("noop\n");
+                cycles += 1;
+            } else {
+                program.push_str(&format!("addx {}\n", rng.range(0..=20) as i64 - 10));
+                cycles += 2;
             }
-        };
+        }
+        Some(program)
+    }
+}
 
-        for cycle in 1..=220 {
-            match state {
-                State::Idle => {
-                    check(cycle, x);
-                    match ops.next().unwrap() {
-                        Op::Noop => (),
-                        Op::Addx(v) => state = State::Adding(v),
-                    }
-                }
-                State::Adding(v) => {
-                    check(cycle, x);
-                    state = State::Idle;
-                    x += v;
+impl Stream for Day10 {
+    fn stream_part_1(&self, input: &mut dyn BufRead) -> io::Result<String> {
+        stream_ops(input, signal_strength)
+    }
+
+    fn stream_part_2(&self, input: &mut dyn BufRead) -> io::Result<String> {
+        stream_ops(input, draw)
+    }
+}
+
+/// The sum of the signal strengths during the 20th, 60th, ... 220th cycles, unless the ops run out.
+fn signal_strength(ops: &mut dyn Iterator<Item = Op>) -> Option<String> {
+    let mut x = 1;
+    let mut state = State::Idle;
+
+    let mut sum = 0;
+    let mut check = |cycle: isize, x: isize| {
+        if (cycle - 20) % 40 == 0 {
+            sum += cycle * x;
+        }
+    };
+
+    for cycle in 1..=220 {
+        match state {
+            State::Idle => {
+                check(cycle, x);
+                match ops.next()? {
+                    Op::Noop => (),
+                    Op::Addx(v) => state = State::Adding(v),
                 }
             }
+            State::Adding(v) => {
+                check(cycle, x);
+                state = State::Idle;
+                x += v;
+            }
         }
-        sum.to_string()
     }
+    Some(sum.to_string())
+}
 
-    fn part_2(&self, input: &str) -> String {
-        let mut ops = parse_to_iter(input);
-
-        let mut x: isize = 1;
-        let mut state = State::Idle;
-
-        let mut out = String::with_capacity(41 * 6);
-        for _ in 0..6 {
-            for col in 0..40 {
-                if x.abs_diff(col) <= 1 {
-                    out.push('#');
-                } else {
-                    out.push(' ');
-                }
-                match state {
-                    State::Idle => match ops.next().unwrap() {
-                        Op::Noop => (),
-                        Op::Addx(v) => state = State::Adding(v),
-                    },
-                    State::Adding(v) => {
-                        state = State::Idle;
-                        x += v;
-                    }
+/// What the CRT draws, unless the ops run out.
+fn draw(ops: &mut dyn Iterator<Item = Op>) -> Option<String> {
+    let mut x: isize = 1;
+    let mut state = State::Idle;
+
+    let mut out = String::with_capacity(41 * 6);
+    for _ in 0..6 {
+        for col in 0..40 {
+            if x.abs_diff(col) <= 1 {
+                out.push('#');
+            } else {
+                out.push(' ');
+            }
+            match state {
+                State::Idle => match ops.next()? {
+                    Op::Noop => (),
+                    Op::Addx(v) => state = State::Adding(v),
+                },
+                State::Adding(v) => {
+                    state = State::Idle;
+                    x += v;
                 }
             }
-            out.push('\n');
         }
-        out
+        out.push('\n');
     }
+    Some(out)
 }
 
 fn parse_to_iter(input: &str) -> impl Iterator<Item = Op> + '_ {
-    input.lines().map(|line| match line {
-        "noop" => Op::Noop,
-        _ => Op::Addx(lexical::parse(line.split_at(5).1).unwrap()),
-    })
+    input
+        .lines()
+        .map(|line| parse_op(line).unwrap_or_else(|error| panic!("{error}")))
+}
+
+fn parse_op(line: &str) -> Result<Op, String> {
+    match line {
+        "noop" => Ok(Op::Noop),
+        _ => line
+            .strip_prefix("addx ")
+            .and_then(|value| lexical::parse(value).ok())
+            .map(Op::Addx)
+            .ok_or_else(|| format!("`{line}` is not an instruction")),
+    }
+}
+
+/// Hands `solve` the ops as they are read, stopping at the first line that could not be read or
+/// is not an op, and reporting it.
+fn stream_ops(
+    input: &mut dyn BufRead,
+    solve: fn(&mut dyn Iterator<Item = Op>) -> Option<String>,
+) -> io::Result<String> {
+    let mut error = None;
+    let mut ops = input.lines().map_while(|line| {
+        let op = line.and_then(|line| parse_op(&line).map_err(stream::invalid));
+        op.map_err(|e| error = Some(e)).ok()
+    });
+    let answer = solve(&mut ops);
+    match (error, answer) {
+        (Some(error), _) => Err(error),
+        (None, Some(answer)) => Ok(answer),
+        (None, None) => Err(stream::invalid(TOO_SHORT)),
+    }
+}
+
+#[cfg(test)]
+mod day_10_tests {
+    use super::*;
+    use crate::days::SNAPSHOTS;
+    use crate::snapshot::assert_snapshot;
+
+    #[test]
+    fn crt_renders() {
+        let program: Vec<&str> = (0..240)
+            .map(|op| match op % 7 {
+                0 => "addx 3",
+                4 => "addx -2",
+                _ => "noop",
+            })
+            .collect();
+        let crt = Day10.part_2(&program.join("\n"));
+        assert_snapshot(SNAPSHOTS, "day_10_crt", &crt);
+    }
+
+    #[test]
+    fn streams_report_bad_programs() {
+        let stream = |input: &[u8]| stream::solve(&Day10, 1, &mut &input[..]).unwrap_err();
+        let program = "noop\n".repeat(240);
+        assert_eq!(
+            stream(program.replacen("noop", "addx one", 3).as_bytes()).to_string(),
+            "`addx one` is not an instruction"
+        );
+        assert_eq!(stream(b"noop\nnoop\n").to_string(), TOO_SHORT);
+        let mut unreadable = program.into_bytes();
+        unreadable[10] = 0xff;
+        assert!(stream(&unreadable).to_string().contains("UTF-8"));
+    }
 }
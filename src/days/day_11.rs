@@ -1,4 +1,5 @@
 use super::Day;
+use crate::lint::{self, Problem};
 
 #[derive(Debug, Clone)]
 enum OpValue {
@@ -131,6 +132,50 @@ impl Day for Day11 {
             .product::<usize>()
             .to_string()
     }
+
+    fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+        const MONKEY: [&str; 6] = [
+            "Monkey {n}:",
+            "  Starting items: ",
+            "  Operation: new = old ",
+            "  Test: divisible by {n}",
+            "    If true: throw to monkey {n}",
+            "    If false: throw to monkey {n}",
+        ];
+        let mut problems = Vec::new();
+        for (first, monkey) in lint::blocks(input) {
+            let mut line = 0;
+            problems.extend(lint::lines(first, monkey, |text| {
+                line += 1;
+                match line {
+                    2 => {
+                        let items = text
+                            .strip_prefix(MONKEY[1])
+                            .ok_or_else(|| format!("expected `{}`", MONKEY[1].trim()))?;
+                        items
+                            .split(", ")
+                            .try_for_each(|item| lint::scan(item, "{n}").map(drop))
+                    }
+                    3 => {
+                        let operation = text
+                            .strip_prefix(MONKEY[2])
+                            .ok_or_else(|| format!("expected `{}`", MONKEY[2].trim()))?;
+                        match operation.split_once(' ') {
+                            Some(("+" | "*", "old")) => Ok(()),
+                            Some(("+" | "*", operand)) => lint::scan(operand, "{n}").map(drop),
+                            _ => Err(String::from("expected `+` or `*` and an operand")),
+                        }
+                    }
+                    1..=6 => lint::scan(text, MONKEY[line - 1]).map(drop),
+                    _ => Err(String::from("expected a blank line after each monkey")),
+                }
+            }));
+            if line < MONKEY.len() && !monkey.trim().is_empty() {
+                problems.push(Problem::new(first + line, "the monkey is cut short"));
+            }
+        }
+        Some(problems)
+    }
 }
 
 fn parse_monkeys(input: &str) -> Vec<Monkey> {
@@ -210,3 +255,55 @@ fn simulate<const R: usize>(monkeys: &mut [Monkey], manage: &Manage) {
         }
     }
 }
+
+#[cfg(test)]
+mod day_11_tests {
+    use super::*;
+    use crate::days::SNAPSHOTS;
+    use crate::snapshot::assert_snapshot;
+
+    const MONKEYS: &str = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1
+";
+
+    #[test]
+    fn monkeys_render() {
+        let mut monkeys = parse_monkeys(MONKEYS);
+        let render = |monkeys: &[Monkey]| {
+            let lines: Vec<String> = monkeys.iter().map(Monkey::to_string).collect();
+            lines.join("\n") + "\n"
+        };
+        assert_snapshot(SNAPSHOTS, "day_11_monkeys_start", &render(&monkeys));
+        let mut thrown = vec![Vec::new(); monkeys.len()];
+        monkeys[0].inspect(&mut thrown, &Manage::DivThree);
+        for (monkey, received) in monkeys.iter_mut().zip(thrown) {
+            monkey.items.extend(received);
+        }
+        assert_snapshot(SNAPSHOTS, "day_11_monkeys_after_0", &render(&monkeys));
+    }
+}
@@ -1,6 +1,8 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 
 use super::Day;
+use crate::lint::{self, Problem};
+use crate::visualize::{Frame, FrameSink, Highlight, NoFrames, Visualize};
 
 type Point = (usize, usize);
 
@@ -8,85 +10,143 @@ pub struct Day12;
 
 impl Day for Day12 {
     fn part_1(&self, input: &str) -> String {
-        let chars: Vec<char> = input.lines().flat_map(str::chars).collect();
-
-        let cols = input.find('\n').unwrap();
-        let rows = chars.len() / cols;
-
-        let start_index = chars.iter().position(|c| *c == 'S').unwrap();
-        let start_point = (start_index % cols, start_index / cols);
-
-        let end_index = chars.iter().position(|c| *c == 'E').unwrap();
-        let end_point = (end_index % cols, end_index / cols);
-
-        let board: Vec<usize> = chars.into_iter().map(char_to_height).collect();
-
-        let mut visited: HashSet<Point> = std::iter::once(start_point).collect();
-        let mut queue: VecDeque<Point> = std::iter::once(start_point).collect();
-        let mut dist: HashMap<Point, usize> = std::iter::once((start_point, 0)).collect();
-
-        while let Some(point @ (x, y)) = queue.pop_front() {
-            if point == end_point {
-                break;
-            }
+        climb(input, &mut NoFrames).to_string()
+    }
 
-            let current_height = board[y * cols + x];
-            for new_point @ (x_n, y_n) in get_new_positions(point, rows, cols) {                
-                let new_height = board[y_n * cols + x_n];
-                if new_height <= current_height + 1 {
-                    if !visited.insert(new_point) {
-                        continue;
-                    }
+    fn part_2(&self, input: &str) -> String {
+        descend(input, &mut NoFrames).to_string()
+    }
 
-                    let current_dist = dist[&point];
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        Some(self)
+    }
 
-                    dist.insert(new_point, current_dist + 1);
-                    queue.push_back(new_point);
-                }
+    fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+        let mut problems = lint::grid(1, input, "abcdefghijklmnopqrstuvwxyzSE");
+        for mark in ['S', 'E'] {
+            let count = input.matches(mark).count();
+            if count != 1 {
+                let message = format!("expected one `{mark}`, found {count}");
+                problems.push(Problem::new(1, message));
             }
         }
-
-        dist[&end_point].to_string()
+        Some(problems)
     }
+}
 
-    fn part_2(&self, input: &str) -> String {
-        let chars: Vec<char> = input.lines().flat_map(str::chars).collect();
-
-        let cols = input.find('\n').unwrap();
-        let rows = chars.len() / cols;
-
-        let start_index = chars.iter().position(|c| *c == 'E').unwrap();
-        let start_point = (start_index % cols, start_index / cols);
+impl Visualize for Day12 {
+    fn visualize(&self, input: &str, sink: &mut dyn FrameSink) {
+        climb(input, sink);
+        descend(input, sink);
+    }
+}
 
-        let board: Vec<usize> = chars.into_iter().map(char_to_height_reverse).collect();
+/// Fewest steps from the start to the best signal.
+fn climb<S: FrameSink + ?Sized>(input: &str, sink: &mut S) -> usize {
+    let chars: Vec<char> = input.lines().flat_map(str::chars).collect();
+    let cols = input.find('\n').unwrap();
+
+    let start_index = chars.iter().position(|c| *c == 'S').unwrap();
+    let end_index = chars.iter().position(|c| *c == 'E').unwrap();
+    let end_point = (end_index % cols, end_index / cols);
+
+    let board: Vec<usize> = chars.iter().copied().map(char_to_height).collect();
+    search(
+        &chars,
+        &board,
+        cols,
+        start_index,
+        |point, _| point == end_point,
+        sink,
+    )
+}
 
-        let mut visited: HashSet<Point> = std::iter::once(start_point).collect();
-        let mut queue: VecDeque<Point> = std::iter::once(start_point).collect();
-        let mut dist: HashMap<Point, usize> = std::iter::once((start_point, 0)).collect();
+/// Fewest steps from any lowest square to the best signal, searching backwards from the end.
+fn descend<S: FrameSink + ?Sized>(input: &str, sink: &mut S) -> usize {
+    let chars: Vec<char> = input.lines().flat_map(str::chars).collect();
+    let cols = input.find('\n').unwrap();
+
+    let start_index = chars.iter().position(|c| *c == 'E').unwrap();
+
+    let board: Vec<usize> = chars.iter().copied().map(char_to_height_reverse).collect();
+    search(
+        &chars,
+        &board,
+        cols,
+        start_index,
+        |_, height| height == 25,
+        sink,
+    )
+}
 
-        while let Some(point @ (x, y)) = queue.pop_front() {
-            let current_height = board[y * cols + x];
-            if current_height == 25 {
-                return dist[&point].to_string();
+/// Breadth first search until `is_goal` accepts a point, emitting a frame per distance explored.
+fn search<S: FrameSink + ?Sized>(
+    chars: &[char],
+    board: &[usize],
+    cols: usize,
+    start_index: usize,
+    is_goal: impl Fn(Point, usize) -> bool,
+    sink: &mut S,
+) -> usize {
+    let rows = chars.len() / cols;
+    let start_point = (start_index % cols, start_index / cols);
+
+    let mut visited: HashSet<Point> = std::iter::once(start_point).collect();
+    let mut queue: VecDeque<Point> = std::iter::once(start_point).collect();
+    let mut dist: HashMap<Point, usize> = std::iter::once((start_point, 0)).collect();
+    let mut shown = None;
+
+    while let Some(point @ (x, y)) = queue.pop_front() {
+        let current_height = board[y * cols + x];
+        let current_dist = dist[&point];
+        if sink.is_recording() && shown != Some(current_dist) {
+            shown = Some(current_dist);
+            sink.emit(search_frame(chars, cols, &visited, &queue, current_dist));
+        }
+        if is_goal(point, current_height) {
+            if sink.is_recording() {
+                let frame = search_frame(chars, cols, &visited, &queue, current_dist).with_overlay(
+                    point,
+                    chars[y * cols + x],
+                    Highlight::Primary,
+                );
+                sink.emit(frame);
             }
+            return current_dist;
+        }
 
-            for new_point @ (x_n, y_n) in get_new_positions(point, rows, cols) {
-                let new_height = board[y_n * cols + x_n];
-                if new_height <= current_height + 1 {
-                    if !visited.insert(new_point) {
-                        continue;
-                    }
-
-                    let current_dist = dist[&point];
-
-                    dist.insert(new_point, current_dist + 1);
-                    queue.push_back(new_point);
+        for new_point @ (x_n, y_n) in get_new_positions(point, rows, cols) {
+            let new_height = board[y_n * cols + x_n];
+            if new_height <= current_height + 1 {
+                if !visited.insert(new_point) {
+                    continue;
                 }
+
+                dist.insert(new_point, current_dist + 1);
+                queue.push_back(new_point);
             }
         }
+    }
 
-        unreachable!()
+    unreachable!()
+}
+
+fn search_frame(
+    chars: &[char],
+    cols: usize,
+    visited: &HashSet<Point>,
+    queue: &VecDeque<Point>,
+    dist: usize,
+) -> Frame {
+    let grid = chars.chunks(cols).map(<[char]>::to_vec).collect();
+    let mut frame = Frame::new(grid).with_caption(format!("distance {dist}"));
+    for &point @ (x, y) in visited {
+        frame.overlay(point, chars[y * cols + x], Highlight::Secondary);
+    }
+    for &point @ (x, y) in queue {
+        frame.overlay(point, chars[y * cols + x], Highlight::Primary);
     }
+    frame
 }
 
 fn char_to_height(c: char) -> usize {
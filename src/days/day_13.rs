@@ -1,57 +1,84 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, slice};
 
 use super::Day;
+use crate::lint::{self, Problem};
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug)]
 enum Value {
     Int(usize),
     List(Vec<Value>),
 }
 
+// Packets can nest far deeper than the call stack, so everything that walks one does so with an
+// explicit stack instead of recursing.
 impl Value {
     fn new(input: &str) -> Self {
-        if input.starts_with('[') {
-            // list
-            let mut values = Vec::new();
-            let input = input.strip_prefix('[').unwrap().strip_suffix(']').unwrap();
-            let mut buf = String::new();
-            let mut level = 0;
-            for c in input.chars() {
-                match c {
-                    '[' => {
-                        level += 1;
-                        buf.push('[');
-                    }
-                    ']' => {
-                        level -= 1;
-                        buf.push(']');
-                    }
-                    ',' => {
-                        if level == 0 {
-                            values.push(Value::new(&buf));
-                            buf.clear();
-                        } else {
-                            buf.push(',');
-                        }
-                    }
-                    c => buf.push(c),
+        let mut lists: Vec<Vec<Value>> = Vec::new();
+        let mut done = None;
+        let mut rest = input.trim_end();
+        while let Some(c) = rest.chars().next() {
+            let value = match c {
+                '[' => {
+                    lists.push(Vec::new());
+                    rest = &rest[1..];
+                    continue;
                 }
+                ']' => {
+                    rest = &rest[1..];
+                    Self::List(lists.pop().unwrap())
+                }
+                ',' => {
+                    rest = &rest[1..];
+                    continue;
+                }
+                _ => {
+                    let end = rest.find([',', ']']).unwrap_or(rest.len());
+                    let int = rest[..end].parse().unwrap();
+                    rest = &rest[end..];
+                    Self::Int(int)
+                }
+            };
+            match lists.last_mut() {
+                Some(list) => list.push(value),
+                None => done = Some(value),
             }
-            if !buf.is_empty() {
-                values.push(Value::new(&buf));
+        }
+        done.unwrap()
+    }
+}
+
+impl Drop for Value {
+    fn drop(&mut self) {
+        if let Value::List(list) = self {
+            let mut pending = std::mem::take(list);
+            while let Some(mut value) = pending.pop() {
+                if let Value::List(list) = &mut value {
+                    pending.append(list);
+                }
             }
-            Self::List(values)
-        } else {
-            // single
-            Self::Int(input.parse().unwrap())
         }
     }
+}
 
-    fn new_list_single(int: usize) -> Self {
-        Self::List(vec![Self::Int(int)])
+// structural, unlike `cmp`, which only in comparing takes an integer for a list holding it
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        let mut pending = vec![(self, other)];
+        while let Some(pair) = pending.pop() {
+            match pair {
+                (Value::Int(left), Value::Int(right)) if left == right => {}
+                (Value::List(left), Value::List(right)) if left.len() == right.len() => {
+                    pending.extend(left.iter().zip(right));
+                }
+                _ => return false,
+            }
+        }
+        true
     }
 }
 
+impl Eq for Value {}
+
 impl PartialOrd for Value {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -60,30 +87,64 @@ impl PartialOrd for Value {
 
 impl Ord for Value {
     fn cmp(&self, other: &Self) -> Ordering {
-        match (self, other) {
-            (Value::Int(left), Value::Int(right)) => left.cmp(right),
-            (Value::Int(left), right) => Value::new_list_single(*left).cmp(right),
-            (left, Value::Int(right)) => left.cmp(&Value::new_list_single(*right)),
-            (Value::List(left), Value::List(right)) => {
-                for i in 0..left.len().max(right.len()) {
-                    match (left.get(i), right.get(i)) {
-                        (Some(left), Some(right)) => match left.cmp(right) {
-                            Ordering::Less => return Ordering::Less, // in order
-                            Ordering::Equal => (),
-                            Ordering::Greater => return Ordering::Greater, // not in order
-                        },
-                        (Some(_), None) => return Ordering::Greater, // right ran out first -> not in order
-                        (None, Some(_)) => return Ordering::Less, // left ran out first -> in order
-                        (None, None) => unreachable!(),
-                    }
+        // the rest of each pair of lists being compared, innermost last; an integer compared
+        // with a list is compared as a list holding only it
+        let mut pending = vec![(slice::from_ref(self), slice::from_ref(other))];
+        while let Some((left, right)) = pending.pop() {
+            let ((first_left, left), (first_right, right)) =
+                match (left.split_first(), right.split_first()) {
+                    (Some(left), Some(right)) => (left, right),
+                    (None, None) => continue,
+                    (None, Some(_)) => return Ordering::Less, // left ran out first -> in order
+                    (Some(_), None) => return Ordering::Greater, // right ran out first -> not in order
+                };
+            pending.push((left, right));
+            match (first_left, first_right) {
+                (Value::Int(left), Value::Int(right)) => match left.cmp(right) {
+                    Ordering::Equal => (),
+                    order => return order,
+                },
+                (Value::Int(_), Value::List(right)) => {
+                    pending.push((slice::from_ref(first_left), right));
+                }
+                (Value::List(left), Value::Int(_)) => {
+                    pending.push((left, slice::from_ref(first_right)));
                 }
-                Ordering::Equal
+                (Value::List(left), Value::List(right)) => pending.push((left, right)),
             }
         }
+        Ordering::Equal
     }
 }
 pub struct Day13;
 
+/// Checks that the line is a single, well formed packet.
+fn lint_packet(line: &str) -> Result<(), String> {
+    if !line.starts_with('[') {
+        return Err(String::from("a packet should start with `[`"));
+    }
+    let mut depth = 0;
+    for (column, c) in line.chars().enumerate() {
+        match c {
+            '[' => depth += 1,
+            ']' if depth == 0 => return Err(format!("unmatched `]` at column {}", column + 1)),
+            ']' => depth -= 1,
+            ',' | '0'..='9' => {}
+            _ => return Err(format!("unexpected {c:?} at column {}", column + 1)),
+        }
+        if depth == 0 && column + 1 < line.len() {
+            return Err(format!(
+                "unexpected text after the packet at column {}",
+                column + 2
+            ));
+        }
+    }
+    match depth {
+        0 => Ok(()),
+        _ => Err(format!("{depth} unclosed `[`")),
+    }
+}
+
 impl Day for Day13 {
     fn part_1(&self, input: &str) -> String {
         input
@@ -126,6 +187,19 @@ impl Day for Day13 {
             .product::<usize>()
             .to_string()
     }
+
+    fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+        let mut problems = Vec::new();
+        for (first, pair) in lint::blocks(input) {
+            problems.extend(lint::lines(first, pair, lint_packet));
+            let packets = pair.lines().count();
+            if packets != 2 {
+                let message = format!("expected a pair of packets, found {packets}");
+                problems.push(Problem::new(first, message));
+            }
+        }
+        Some(problems)
+    }
 }
 
 #[cfg(test)]
@@ -150,6 +224,15 @@ mod day_13_tests {
         );
     }
 
+    #[test]
+    fn equality_is_structural() {
+        let (int, list) = (Value::new("1"), Value::new("[1]"));
+        assert_eq!(int.cmp(&list), Ordering::Equal);
+        assert_ne!(int, list);
+        assert_ne!(Value::new("[1,[2]]"), Value::new("[1,2]"));
+        assert_ne!(Value::new("[[]]"), Value::new("[[],[]]"));
+    }
+
     #[test]
     fn test_simple_order() {
         // 1
@@ -192,4 +275,19 @@ mod day_13_tests {
         let right = Value::new("[1,[2,[3,[4,[5,6,0]]]],8,9]");
         assert_eq!(left.cmp(&right), Ordering::Greater);
     }
+
+    #[test]
+    fn deep_packets() {
+        let depth = 1_000_000;
+        let deep = |inner: &str| format!("{}{inner}{}", "[".repeat(depth), "]".repeat(depth));
+        let left = Value::new(&deep("1"));
+        let right = Value::new(&deep("2"));
+        assert_eq!(left.cmp(&right), Ordering::Less);
+        assert_eq!(left.cmp(&Value::new(&deep("1"))), Ordering::Equal);
+        assert_eq!(left, Value::new(&deep("1")));
+        assert_ne!(left, right);
+        assert_eq!(left.cmp(&Value::new("[[[0]]]")), Ordering::Greater);
+        let input = format!("{}\n{}\n", deep(""), deep("[]"));
+        assert_eq!(Day13.part_1(&input), "1");
+    }
 }
@@ -1,6 +1,8 @@
 use std::{cmp::Ordering, collections::HashSet};
 
 use super::Day;
+use crate::lint::{self, Problem};
+use crate::visualize::{Frame, FrameSink, Highlight, NoFrames, Visualize};
 
 const WIDTH: usize = 1000;
 const HEIGHT: usize = 1000;
@@ -15,6 +17,9 @@ enum Tile {
 struct Board {
     grid: Vec<Tile>,
     count: usize,
+    /// Leftmost and rightmost columns that hold anything, for framing
+    min_x: usize,
+    max_x: usize,
 }
 
 impl Board {
@@ -22,6 +27,8 @@ impl Board {
         Self {
             grid: vec![Tile::Air; WIDTH * HEIGHT],
             count: 0,
+            min_x: 500,
+            max_x: 500,
         }
     }
 
@@ -31,13 +38,41 @@ impl Board {
     }
 
     fn set_rock(&mut self, (x, y): (usize, usize)) {
+        self.min_x = self.min_x.min(x);
+        self.max_x = self.max_x.max(x);
         self.grid[y * WIDTH + x] = Tile::Rock;
     }
 
     fn set_sand(&mut self, (x, y): (usize, usize)) {
         self.count += 1;
+        self.min_x = self.min_x.min(x);
+        self.max_x = self.max_x.max(x);
         self.grid[y * WIDTH + x] = Tile::Sand;
     }
+
+    /// Frames everything down to `depth`, with the path of the falling sand overlaid.
+    fn frame(&self, depth: usize, floor: bool, falling: &[(usize, usize)]) -> Frame {
+        let left = self.min_x - 1;
+        let grid = (0..=depth)
+            .map(|y| {
+                (left..=self.max_x + 1)
+                    .map(|x| match self.grid[y * WIDTH + x] {
+                        Tile::Air if floor && y == depth => '#',
+                        Tile::Air => '.',
+                        Tile::Sand => 'o',
+                        Tile::Rock => '#',
+                    })
+                    .collect()
+            })
+            .collect();
+        let mut frame = Frame::new(grid)
+            .with_overlay((500 - left, 0), '+', Highlight::Secondary)
+            .with_caption(format!("{} units of sand at rest", self.count));
+        for &(x, y) in falling {
+            frame.overlay((x - left, y), '~', Highlight::Muted);
+        }
+        frame
+    }
 }
 
 pub struct Day14;
@@ -45,80 +80,92 @@ pub struct Day14;
 impl Day for Day14 {
     fn part_1(&self, input: &str) -> String {
         let (mut board, depth) = parse_input(input);
-        let mut to_drop: Vec<(usize, usize)> = std::iter::once((500, 0)).collect();
-        while let Some(current) = to_drop.pop() {
-            if current.1 >= depth {
-                break;
-            }
+        pour(&mut board, depth, false, &mut NoFrames).to_string()
+    }
 
-            // straight down;
-            let new = (current.0, current.1 + 1);
-            if !board.is_occupied(new) {
-                to_drop.push(current);
-                to_drop.push(new);
-                continue;
-            }
+    fn part_2(&self, input: &str) -> String {
+        let (mut board, depth) = parse_input(input);
+        pour(&mut board, depth, true, &mut NoFrames).to_string()
+    }
 
-            // lower left
-            let new = (current.0 - 1, current.1 + 1);
-            if !board.is_occupied(new) {
-                to_drop.push(current);
-                to_drop.push(new);
-                continue;
-            }
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        Some(self)
+    }
 
-            // lower right
-            let new = (current.0 + 1, current.1 + 1);
-            if !board.is_occupied(new) {
-                to_drop.push(current);
-                to_drop.push(new);
-                continue;
+    fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+        Some(lint::lines(1, input, |path| {
+            let points: Vec<&str> = path.split(" -> ").collect();
+            if points.len() < 2 {
+                return Err(String::from("expected at least two points"));
             }
-
-            // no moves left
-            board.set_sand(current);
-        }
-        board.count.to_string()
+            points
+                .iter()
+                .try_for_each(|point| lint::scan(point, "{n},{n}").map(drop))
+        }))
     }
+}
 
-    fn part_2(&self, input: &str) -> String {
+impl Visualize for Day14 {
+    fn visualize(&self, input: &str, sink: &mut dyn FrameSink) {
         let (mut board, depth) = parse_input(input);
-        let depth = depth + 2;
-        let mut to_drop: Vec<(usize, usize)> = std::iter::once((500, 0)).collect();
-        while let Some(current) = to_drop.pop() {
-            if current.1 == depth - 1 {
-                board.set_sand(current);
-                continue;
-            }
+        pour(&mut board, depth, false, sink);
+        let (mut board, depth) = parse_input(input);
+        pour(&mut board, depth, true, sink);
+    }
+}
 
-            // straight down;
-            let new = (current.0, current.1 + 1);
-            if !board.is_occupied(new) {
-                to_drop.push(current);
-                to_drop.push(new);
-                continue;
+/// Drops sand until it falls into the abyss, or until the source is blocked when there is a floor.
+fn pour<S: FrameSink + ?Sized>(
+    board: &mut Board,
+    depth: usize,
+    floor: bool,
+    sink: &mut S,
+) -> usize {
+    let depth = if floor { depth + 2 } else { depth };
+    let mut to_drop: Vec<(usize, usize)> = std::iter::once((500, 0)).collect();
+    while let Some(current) = to_drop.pop() {
+        if floor && current.1 == depth - 1 {
+            board.set_sand(current);
+            if sink.is_recording() {
+                sink.emit(board.frame(depth, floor, &to_drop));
             }
+            continue;
+        }
+        if !floor && current.1 >= depth {
+            break;
+        }
 
-            // lower left
-            let new = (current.0 - 1, current.1 + 1);
-            if !board.is_occupied(new) {
-                to_drop.push(current);
-                to_drop.push(new);
-                continue;
-            }
+        // straight down;
+        let new = (current.0, current.1 + 1);
+        if !board.is_occupied(new) {
+            to_drop.push(current);
+            to_drop.push(new);
+            continue;
+        }
 
-            // lower right
-            let new = (current.0 + 1, current.1 + 1);
-            if !board.is_occupied(new) {
-                to_drop.push(current);
-                to_drop.push(new);
-                continue;
-            }
+        // lower left
+        let new = (current.0 - 1, current.1 + 1);
+        if !board.is_occupied(new) {
+            to_drop.push(current);
+            to_drop.push(new);
+            continue;
+        }
 
-            board.set_sand(current);
+        // lower right
+        let new = (current.0 + 1, current.1 + 1);
+        if !board.is_occupied(new) {
+            to_drop.push(current);
+            to_drop.push(new);
+            continue;
+        }
+
+        // no moves left
+        board.set_sand(current);
+        if sink.is_recording() {
+            sink.emit(board.frame(depth, floor, &to_drop));
         }
-        board.count.to_string()
     }
+    board.count
 }
 
 fn parse_input(input: &str) -> (Board, usize) {
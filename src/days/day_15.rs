@@ -8,6 +8,7 @@ use nom::{
 };
 
 use super::Day;
+use crate::lint::{self, Problem};
 
 const PART_ONE_ROW: i64 = 2_000_000;
 const MAX_DISTANCE: i64 = 4_000_000;
@@ -194,6 +195,16 @@ impl Day for Day15 {
         }
         unreachable!()
     }
+
+    fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+        Some(lint::lines(1, input, |line| {
+            lint::scan(
+                line,
+                "Sensor at x={i}, y={i}: closest beacon is at x={i}, y={i}",
+            )
+            .map(drop)
+        }))
+    }
 }
 
 /// Finds the point within the search area and returns its tuning.
@@ -1,9 +1,11 @@
 use std::{
     collections::{BinaryHeap, HashMap, HashSet, VecDeque},
     hash::Hash,
+    time::Duration,
 };
 
 use super::Day;
+use crate::lint::{self, Problem};
 
 #[derive(Debug, PartialEq)]
 struct Valve<'a> {
@@ -112,6 +114,27 @@ impl Day for Day16 {
         let basic_map = get_reduced_map(&valves);
         traverse_double(&basic_map).to_string()
     }
+
+    fn budget(&self) -> Duration {
+        // part 2 takes minutes
+        Duration::from_secs(10 * 60)
+    }
+
+    fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+        Some(lint::lines(1, input, |line| {
+            let valve = |name: &str| match name.as_bytes() {
+                [b'A'..=b'Z', b'A'..=b'Z'] => Ok(()),
+                _ => Err(format!("`{name}` is not a valve")),
+            };
+            let (start, tunnels) = line
+                .split_once("; tunnels lead to valves ")
+                .or_else(|| line.split_once("; tunnel leads to valve "))
+                .ok_or("expected `; tunnels lead to valves`")?;
+            let fields = lint::scan(start, "Valve {w} has flow rate={n}")?;
+            valve(fields[0])?;
+            tunnels.split(", ").try_for_each(valve)
+        }))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
@@ -416,4 +439,16 @@ mod day_16_tests {
             }
         )
     }
+
+    #[test]
+    fn lints_valves() {
+        let input = "Valve AA has flow rate=0; tunnels lead to valves DD, II\n\
+                     Valve BB has flow rate=13; tunnel leads to valve CC\n\
+                     Valve CC has flow rate=2; tunnels lead to DD\n\
+                     Valve dd has flow rate=20; tunnel leads to valve CC\n";
+        let problems = Day16.lint(input).unwrap();
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].line, 3);
+        assert_eq!(problems[1], Problem::new(4, "`dd` is not a valve"));
+    }
 }
@@ -1,4 +1,6 @@
 use super::Day;
+use crate::lint::{self, Problem};
+use crate::visualize::{Frame, FrameSink, Highlight, NoFrames, Visualize};
 
 type Point = (usize, usize);
 
@@ -182,6 +184,31 @@ impl Chamber {
     fn total_height(&self) -> usize {
         self.height_before_floor + self.height
     }
+
+    /// Frames the top `rows` rows, including the space above the tower where rocks appear.
+    fn frame(&self, rows: usize) -> Frame {
+        let top = self.rock.y + 4;
+        let bottom = top.saturating_sub(rows);
+        let grid = (bottom..top)
+            .rev()
+            .map(|y| {
+                std::iter::once('|')
+                    .chain((0..7).map(|x| match self.get((x, y)) {
+                        Tile::Air => '.',
+                        Tile::Rock => '#',
+                    }))
+                    .chain(std::iter::once('|'))
+                    .collect()
+            })
+            .collect();
+        let mut frame = Frame::new(grid);
+        for (x, y) in self.rock.get_points() {
+            if (bottom..top).contains(&y) {
+                frame.overlay((x + 1, top - 1 - y), '@', Highlight::Primary);
+            }
+        }
+        frame
+    }
 }
 
 impl std::fmt::Display for Chamber {
@@ -211,21 +238,7 @@ pub struct Day17;
 
 impl Day for Day17 {
     fn part_1(&self, input: &str) -> String {
-        let mut ops = input.chars().cycle();
-        let mut chamber = Chamber::new();
-        let mut count: usize = 0;
-        while count < 2022 {
-            match ops.next().unwrap() {
-                '>' => chamber.move_right(),
-                '<' => chamber.move_left(),
-                _ => unreachable!(),
-            }
-            if chamber.move_down() {
-                count += 1;
-            }
-        }
-
-        chamber.height.to_string()
+        drop_rocks(input, 2022, &mut NoFrames).height.to_string()
     }
 
     fn part_2(&self, input: &str) -> String {
@@ -274,11 +287,51 @@ impl Day for Day17 {
         let total_height = offset_height + cycle_height + remaining_height;
         total_height.to_string()
     }
+
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        Some(self)
+    }
+
+    fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+        Some(lint::single_line(1, input, "<>"))
+    }
+}
+
+impl Visualize for Day17 {
+    fn visualize(&self, input: &str, sink: &mut dyn FrameSink) {
+        drop_rocks(input, 2022, sink);
+    }
+}
+
+/// Lets `rocks` rocks come to rest, emitting a frame each time one lands.
+fn drop_rocks<S: FrameSink + ?Sized>(input: &str, rocks: usize, sink: &mut S) -> Chamber {
+    let mut ops = input.chars().cycle();
+    let mut chamber = Chamber::new();
+    let mut count: usize = 0;
+    while count < rocks {
+        match ops.next().unwrap() {
+            '>' => chamber.move_right(),
+            '<' => chamber.move_left(),
+            _ => unreachable!(),
+        }
+        if chamber.move_down() {
+            count += 1;
+            if sink.is_recording() {
+                sink.emit(chamber.frame(30).with_caption(format!(
+                    "{count} rocks, tower height {}",
+                    chamber.total_height()
+                )));
+            }
+        }
+    }
+    chamber
 }
 
 #[cfg(test)]
 mod day_17_tests {
     use super::*;
+    use crate::days::SNAPSHOTS;
+    use crate::snapshot::assert_snapshot;
 
     #[test]
     fn test_move_down() {
@@ -323,4 +376,22 @@ mod day_17_tests {
         assert_eq!(chamber.total_height(), 3);
         assert_eq!(chamber.rock.y, 3);
     }
+
+    #[test]
+    fn lints_jets() {
+        assert_eq!(Day17.lint(">><<>\n"), Some(vec![]));
+        assert_eq!(
+            Day17.lint("><v>\n"),
+            Some(vec![Problem::new(
+                1,
+                "unexpected 'v', expected one of `<>`"
+            )])
+        );
+    }
+
+    #[test]
+    fn chamber_renders() {
+        let chamber = drop_rocks(">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>", 5, &mut NoFrames);
+        assert_snapshot(SNAPSHOTS, "day_17_chamber", &chamber.to_string());
+    }
 }
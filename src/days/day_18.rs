@@ -1,6 +1,7 @@
 use std::collections::{HashSet, VecDeque};
 
 use super::Day;
+use crate::lint::{self, Problem};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash, Ord)]
 struct Point {
@@ -133,6 +134,12 @@ impl Day for Day18 {
         }
         total.to_string()
     }
+
+    fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+        Some(lint::lines(1, input, |cube| {
+            lint::scan(cube, "{n},{n},{n}").map(drop)
+        }))
+    }
 }
 
 fn find_outside(
@@ -4,8 +4,10 @@ use std::{
 };
 
 use rayon::prelude::*;
+use tracing::{debug, debug_span};
 
 use super::Day;
+use crate::lint::{self, Problem};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 struct Costs {
@@ -229,6 +231,7 @@ impl Day for Day19 {
         let total: usize = blueprints
             .into_par_iter()
             .map(|blueprint| {
+                let _span = debug_span!("blueprint", id = blueprint.id).entered();
                 let mut queue = BinaryHeap::new();
                 queue.push(blueprint);
                 let max_geode = AtomicUsize::new(0);
@@ -256,6 +259,7 @@ impl Day for Day19 {
                         });
                     queue.par_extend(new);
                 }
+                debug!(geodes = max_geode.load(Ordering::Acquire), "opened");
                 blueprint.id * max_geode.load(Ordering::Acquire)
             })
             .sum();
@@ -268,6 +272,7 @@ impl Day for Day19 {
             .into_par_iter()
             .take(3)
             .map(|blueprint| {
+                let _span = debug_span!("blueprint", id = blueprint.id).entered();
                 let mut queue = BinaryHeap::new();
                 queue.push(blueprint);
                 let max_geode = AtomicUsize::new(0);
@@ -295,11 +300,23 @@ impl Day for Day19 {
                         });
                     queue.par_extend(new);
                 }
+                debug!(geodes = max_geode.load(Ordering::Acquire), "opened");
                 max_geode.load(Ordering::Acquire)
             })
             .product();
         total.to_string()
     }
+
+    fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+        const BLUEPRINT: &str = "Blueprint {n}: \
+            Each ore robot costs {n} ore. \
+            Each clay robot costs {n} ore. \
+            Each obsidian robot costs {n} ore and {n} clay. \
+            Each geode robot costs {n} ore and {n} obsidian.";
+        Some(lint::lines(1, input, |line| {
+            lint::scan(line, BLUEPRINT).map(drop)
+        }))
+    }
 }
 
 fn parse_blueprints<const R: usize>(input: &str) -> Vec<Blueprint<R>> {
@@ -1,4 +1,7 @@
+use tracing::{debug, debug_span};
+
 use super::Day;
+use crate::lint::{self, Problem};
 
 const KEY: isize = 811_589_153;
 
@@ -25,7 +28,7 @@ impl Day for Day20 {
             .map(|i| (i + index) % max)
             .map(|i| {
                 let n = numbers[new[i]];
-                dbg!(n);
+                debug!(n, "grove coordinate");
                 n
             })
             .sum::<isize>()
@@ -39,7 +42,8 @@ impl Day for Day20 {
             .collect::<Vec<_>>();
         let max = numbers.len();
         let mut new = (0..).take(max).collect::<Vec<_>>();
-        for _ in 0..10 {
+        for round in 0..10 {
+            let _span = debug_span!("mix", round).entered();
             for (index, original) in numbers.iter().copied().enumerate() {
                 let start = new.iter().position(|i| *i == index).unwrap();
                 let element = new.remove(start);
@@ -54,10 +58,16 @@ impl Day for Day20 {
             .map(|i| (i + index) % max)
             .map(|i| {
                 let n = numbers[new[i]];
-                dbg!(n);
+                debug!(n, "grove coordinate");
                 n
             })
             .sum::<isize>()
             .to_string()
     }
+
+    fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+        Some(lint::lines(1, input, |number| {
+            lint::scan(number, "{i}").map(drop)
+        }))
+    }
 }
@@ -1,6 +1,7 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use super::Day;
+use crate::lint::{self, Problem};
 
 type Monkeys<'a> = HashMap<&'a str, Value<'a>>;
 
@@ -9,52 +10,56 @@ enum Value<'a> {
     Binary(Op, &'a str, &'a str),
 }
 
-impl<'a> Value<'a> {
+// Monkeys can wait on chains of others far deeper than the call stack, so values are worked out
+// with an explicit stack of tasks instead of by recursing.
+enum Task<'v, 'a> {
+    Evaluate(&'v Value<'a>),
+    Apply(&'v Op),
+}
+
+impl Value<'_> {
     fn get(&self, monkeys: &Monkeys) -> usize {
-        match self {
-            Value::Unary(value) => *value,
-            Value::Binary(op, left, right) => {
-                let left = monkeys[left].get(monkeys);
-                let right = monkeys[right].get(monkeys);
-                match op {
-                    Op::Add => left + right,
-                    Op::Minus => left - right,
-                    Op::Multiply => left * right,
-                    Op::Divide => left / right,
+        let mut tasks = vec![Task::Evaluate(self)];
+        let mut values = Vec::new();
+        while let Some(task) = tasks.pop() {
+            match task {
+                Task::Evaluate(Value::Unary(value)) => values.push(*value),
+                Task::Evaluate(Value::Binary(op, left, right)) => {
+                    tasks.push(Task::Apply(op));
+                    tasks.push(Task::Evaluate(&monkeys[right]));
+                    tasks.push(Task::Evaluate(&monkeys[left]));
+                }
+                Task::Apply(op) => {
+                    let right = values.pop().unwrap();
+                    let left = values.pop().unwrap();
+                    values.push(op.apply(left, right));
                 }
             }
         }
+        values.pop().unwrap()
     }
+}
 
-    fn get_unless_human(&self, monkeys: &Monkeys) -> Option<usize> {
-        match self {
-            Value::Unary(value) => Some(*value),
-            Value::Binary(op, left, right) => {
-                if *left == "humn" || *right == "humn" {
-                    None
-                } else {
-                    let left = monkeys[left].get_unless_human(monkeys);
-                    let right = monkeys[right].get_unless_human(monkeys);
-                    match (left, right) {
-                        (Some(left), Some(right)) => Some(match op {
-                            Op::Add => left + right,
-                            Op::Minus => left - right,
-                            Op::Multiply => left * right,
-                            Op::Divide => left / right,
-                        }),
-                        _ => None,
-                    }
-                }
-            }
+/// The monkeys whose numbers depend on the human's, the human included, found by walking up from
+/// the human once rather than by evaluating each monkey on the way down.
+fn human_side<'a>(monkeys: &Monkeys<'a>) -> HashSet<&'a str> {
+    let mut waiting: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (&monkey, value) in monkeys {
+        if let Value::Binary(_, left, right) = value {
+            waiting.entry(left).or_default().push(monkey);
+            waiting.entry(right).or_default().push(monkey);
         }
     }
-
-    fn contains(&self, target: &'a str) -> bool {
-        match self {
-            Value::Unary(_) => false,
-            Value::Binary(_, left, right) => *left == target || *right == target,
+    let mut side = HashSet::from(["humn"]);
+    let mut pending = vec!["humn"];
+    while let Some(monkey) = pending.pop() {
+        for &waiter in waiting.get(monkey).into_iter().flatten() {
+            if side.insert(waiter) {
+                pending.push(waiter);
+            }
         }
     }
+    side
 }
 
 enum Op {
@@ -64,6 +69,17 @@ enum Op {
     Divide,
 }
 
+impl Op {
+    fn apply(&self, left: usize, right: usize) -> usize {
+        match self {
+            Op::Add => left + right,
+            Op::Minus => left - right,
+            Op::Multiply => left * right,
+            Op::Divide => left / right,
+        }
+    }
+}
+
 pub struct Day21;
 
 impl Day for Day21 {
@@ -74,67 +90,58 @@ impl Day for Day21 {
 
     fn part_2(&self, input: &str) -> String {
         let monkeys = parse_input(input);
-        if let Value::Binary(_, left, right) = monkeys["root"] {
-            let (mut target_value, container) =
-                if let Some(value) = monkeys[left].get_unless_human(&monkeys) {
-                    (value, right)
-                } else {
-                    (monkeys[right].get(&monkeys), left)
+        let human = human_side(&monkeys);
+        let Value::Binary(_, left, right) = monkeys["root"] else {
+            unreachable!()
+        };
+        // root's sides should be equal, so the side without the human is the number to reach
+        let (mut target_value, mut container) = if human.contains(left) {
+            (monkeys[right].get(&monkeys), left)
+        } else {
+            (monkeys[left].get(&monkeys), right)
+        };
+        while container != "humn" {
+            let Value::Binary(op, left, right) = &monkeys[container] else {
+                unreachable!()
+            };
+            if human.contains(left) {
+                let value = monkeys[right].get(&monkeys);
+                target_value = match op {
+                    Op::Add => target_value - value,
+                    Op::Minus => target_value + value,
+                    Op::Multiply => target_value / value,
+                    Op::Divide => value * target_value,
                 };
-            let mut container = container;
-            loop {
-                // dbg!(container);
-                let value = &monkeys[container];
-                if value.contains("humn") {
-                    if let Value::Binary(op, left, right) = value {
-                        if *left == "humn" {
-                            let value = monkeys[right].get(&monkeys);
-                            target_value = match op {
-                                Op::Add => target_value - value,
-                                Op::Minus => target_value + value,
-                                Op::Multiply => target_value / value,
-                                Op::Divide => value * target_value,
-                            };
-                        } else {
-                            let value = monkeys[left].get(&monkeys);
-                            target_value = match op {
-                                Op::Add => target_value - value,
-                                Op::Minus => value - target_value,
-                                Op::Multiply => target_value / value,
-                                Op::Divide => value / target_value,
-                            };
-                        }
-                        break;
-                    }
-                }
-                match value {
-                    Value::Unary(_) => todo!(),
-                    Value::Binary(op, left, right) => {
-                        if let Some(value) = monkeys[left].get_unless_human(&monkeys) {
-                            // dbg!("left does not have human");
-                            target_value = match op {
-                                Op::Add => target_value - value,
-                                Op::Minus => value - target_value,
-                                Op::Multiply => target_value / value,
-                                Op::Divide => value / target_value,
-                            };
-                            container = *right;
-                        } else {
-                            let value = monkeys[right].get(&monkeys);
-                            target_value = match op {
-                                Op::Add => target_value - value,
-                                Op::Minus => target_value + value,
-                                Op::Multiply => target_value / value,
-                                Op::Divide => value * target_value,
-                            };
-                            container = *left;
-                        }
-                    }
-                }
+                container = left;
+            } else {
+                let value = monkeys[left].get(&monkeys);
+                target_value = match op {
+                    Op::Add => target_value - value,
+                    Op::Minus => value - target_value,
+                    Op::Multiply => target_value / value,
+                    Op::Divide => value / target_value,
+                };
+                container = right;
             }
-            return target_value.to_string();
         }
-        unreachable!()
+        target_value.to_string()
+    }
+
+    fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+        Some(lint::lines(1, input, |line| {
+            let (name, job) = line
+                .split_once(": ")
+                .ok_or("expected a monkey's name and `: `")?;
+            lint::scan(name, "{w}")?;
+            match job.split(' ').collect::<Vec<_>>()[..] {
+                [number] => lint::scan(number, "{n}").map(drop),
+                [left, "+" | "-" | "*" | "/", right] => {
+                    lint::scan(left, "{w}")?;
+                    lint::scan(right, "{w}").map(drop)
+                }
+                _ => Err(format!("expected a number or an operation, found `{job}`")),
+            }
+        }))
     }
 }
 
@@ -163,3 +170,38 @@ fn parse_input(input: &str) -> Monkeys {
 
     monkeys
 }
+
+#[cfg(test)]
+mod day_21_tests {
+    use super::*;
+
+    #[test]
+    fn deep_chains() {
+        let depth = 1_000_000;
+        let mut input = String::from("root: goal + m0\ngoal: 2000000\n");
+        for i in 0..depth {
+            input.push_str(&format!("m{i}: one + m{}\n", i + 1));
+        }
+        input.push_str(&format!("m{depth}: one + humn\none: 1\nhumn: 5\n"));
+        let monkeys = parse_input(&input);
+        assert_eq!(monkeys["m0"].get(&monkeys), depth + 6);
+        let human = human_side(&monkeys);
+        assert_eq!(human.len(), depth + 3);
+        assert!(human.contains("m1") && !human.contains("one"));
+        assert_eq!(Day21.part_1(&input), (2_000_000 + depth + 6).to_string());
+        // the chain adds one per monkey to the human's number
+        assert_eq!(Day21.part_2(&input), (2_000_000 - depth - 1).to_string());
+    }
+
+    #[test]
+    fn deep_chains_on_the_left() {
+        let depth = 1_000_000;
+        let mut input = String::from("root: m0 + goal\ngoal: 3000000\n");
+        for i in 0..depth {
+            input.push_str(&format!("m{i}: m{} - one\n", i + 1));
+        }
+        input.push_str(&format!("m{depth}: humn * two\none: 1\ntwo: 2\nhumn: 5\n"));
+        // the chain takes one per monkey from twice the human's number
+        assert_eq!(Day21.part_2(&input), ((3_000_000 + depth) / 2).to_string());
+    }
+}
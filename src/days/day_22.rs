@@ -1,6 +1,8 @@
 use std::{fmt::Display, marker::PhantomData};
 
 use super::Day;
+use crate::lint::{self, Problem};
+use crate::visualize::{Frame, FrameSink, Highlight, NoFrames, Visualize};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum Tile {
@@ -93,6 +95,15 @@ impl<S: Shape> Grove<S> {
         self.get(point) == Tile::Open
     }
 
+    fn facing(&self) -> char {
+        match self.direction {
+            Direction::Up => '^',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+            Direction::Right => '>',
+        }
+    }
+
     fn turn_left(&mut self) {
         self.direction = match self.direction {
             Direction::Up => Direction::Left,
@@ -112,7 +123,11 @@ impl<S: Shape> Grove<S> {
     }
 }
 
-impl Grove<Flat> {
+trait Advance {
+    fn advance(&mut self, step: Step);
+}
+
+impl Advance for Grove<Flat> {
     fn advance(&mut self, step: Step) {
         match step {
             Step::Literal(steps) => {
@@ -127,7 +142,9 @@ impl Grove<Flat> {
             Step::TurnRight => self.turn_right(),
         }
     }
+}
 
+impl Grove<Flat> {
     fn move_forward(&mut self) -> bool {
         let mut new_point = match self.direction {
             Direction::Up => (self.x, self.y - 1),
@@ -181,7 +198,7 @@ impl Grove<Flat> {
     }
 }
 
-impl Grove<Cube> {
+impl Advance for Grove<Cube> {
     fn advance(&mut self, step: Step) {
         match step {
             Step::Literal(steps) => {
@@ -196,7 +213,9 @@ impl Grove<Cube> {
             Step::TurnRight => self.turn_right(),
         }
     }
+}
 
+impl Grove<Cube> {
     fn move_forward(&mut self) -> bool {
         let mut new_point = match self.direction {
             Direction::Up => (self.x, self.y - 1),
@@ -286,13 +305,7 @@ impl<S: Shape> Display for Grove<S> {
         for (y, row) in self.board.iter().enumerate() {
             for (x, tile) in row.iter().enumerate() {
                 if self.y == y && self.x == x {
-                    let c = match self.direction {
-                        Direction::Up => "^",
-                        Direction::Down => "v",
-                        Direction::Left => "<",
-                        Direction::Right => ">",
-                    };
-                    write!(f, "{c}")?;
+                    write!(f, "{}", self.facing())?;
                 } else {
                     write!(f, "{tile}")?;
                 }
@@ -307,34 +320,75 @@ pub struct Day22;
 
 impl Day for Day22 {
     fn part_1(&self, input: &str) -> String {
-        let (map_input, steps_input) = input.split_once("\n\n").unwrap();
-        let mut grove = Grove::<Flat>::new(map_input);
-        for step in parse_steps(steps_input.trim()) {
-            grove.advance(step);
-        }
-        let row = grove.y * 1000;
-        let col = grove.x * 4;
-        let facing = grove.direction as usize;
-
-        let password = row + col + facing;
-        password.to_string()
+        walk::<Flat, _>(input, &mut NoFrames).to_string()
     }
 
     fn part_2(&self, input: &str) -> String {
-        let (map_input, steps_input) = input.split_once("\n\n").unwrap();
-        let mut grove = Grove::<Cube>::new(map_input);
-        for step in parse_steps(steps_input.trim()) {
-            grove.advance(step);
+        walk::<Cube, _>(input, &mut NoFrames).to_string()
+    }
+
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        Some(self)
+    }
+
+    fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+        let blocks = lint::blocks(input);
+        let (map, path) = match blocks[..] {
+            [(_, map), path] => (map, Some(path)),
+            _ => (input, None),
+        };
+        let mut problems = lint::lines(1, map, |row| {
+            match row.chars().find(|&tile| !" .#".contains(tile)) {
+                Some(tile) => Err(format!("unexpected {tile:?}, expected one of ` .#`")),
+                None => Ok(()),
+            }
+        });
+        match path {
+            Some((first, path)) => problems.extend(lint::single_line(first, path, "0123456789LR")),
+            None => {
+                let message = "expected the map and the path, separated by a blank line";
+                problems.push(Problem::new(1, message));
+            }
         }
-        let row = grove.y * 1000;
-        let col = grove.x * 4;
-        let facing = grove.direction as usize;
+        Some(problems)
+    }
+}
 
-        let password = row + col + facing;
-        password.to_string()
+impl Visualize for Day22 {
+    fn visualize(&self, input: &str, sink: &mut dyn FrameSink) {
+        walk::<Flat, _>(input, sink);
+        walk::<Cube, _>(input, sink);
     }
 }
 
+/// Follows the path, returning the final password.
+fn walk<Sh: Shape, S: FrameSink + ?Sized>(input: &str, sink: &mut S) -> usize
+where
+    Grove<Sh>: Advance,
+{
+    let (map_input, steps_input) = input.split_once("\n\n").unwrap();
+    let mut grove = Grove::<Sh>::new(map_input);
+    let mut trail = Vec::new();
+    for step in parse_steps(steps_input.trim()) {
+        if sink.is_recording() {
+            trail.push((grove.x, grove.y));
+        }
+        grove.advance(step);
+        if sink.is_recording() {
+            let mut frame = Frame::from_text(&grove.to_string()).with_caption(format!("{step:?}"));
+            for &point in &trail {
+                frame.overlay(point, '~', Highlight::Muted);
+            }
+            sink.emit(frame.with_overlay((grove.x, grove.y), grove.facing(), Highlight::Primary));
+        }
+    }
+    let row = grove.y * 1000;
+    let col = grove.x * 4;
+    let facing = grove.direction as usize;
+
+    row + col + facing
+}
+
 fn parse_steps(input: &str) -> Vec<Step> {
     let mut steps = Vec::new();
     let mut buf = String::new();
@@ -359,3 +413,33 @@ fn parse_steps(input: &str) -> Vec<Step> {
     }
     steps
 }
+
+#[cfg(test)]
+mod day_22_tests {
+    use super::*;
+    use crate::days::SNAPSHOTS;
+    use crate::snapshot::assert_snapshot;
+
+    const MAP: &str = "        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.";
+
+    #[test]
+    fn grove_renders() {
+        let mut grove = Grove::<Flat>::new(MAP);
+        assert_snapshot(SNAPSHOTS, "day_22_grove_start", &grove.to_string());
+        for step in parse_steps("10R5L5R10") {
+            grove.advance(step);
+        }
+        assert_snapshot(SNAPSHOTS, "day_22_grove_walked", &grove.to_string());
+    }
+}
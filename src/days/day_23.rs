@@ -2,8 +2,11 @@ use std::ops::Add;
 
 use rayon::prelude::*;
 use rustc_hash::FxHashMap;
+use tracing::{trace, trace_span};
 
 use super::Day;
+use crate::lint::{self, Problem};
+use crate::visualize::{Frame, FrameSink, Highlight, NoFrames, Visualize};
 
 // Point(x, y)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -125,11 +128,8 @@ fn generate_proposals(elves: &[Elf], round: usize) -> FxHashMap<Point, ProposalS
         .par_iter()
         .enumerate()
         .filter_map(|(id, elf)| {
-            if let Some(new_point) = elf.propose(&elves, round % 4) {
-                Some((new_point, id))
-            } else {
-                None
-            }
+            elf.propose(elves, round % 4)
+                .map(|new_point| (new_point, id))
         })
         .collect::<Vec<_>>()
         .into_iter()
@@ -163,14 +163,7 @@ pub struct Day23;
 impl Day for Day23 {
     fn part_1(&self, input: &str) -> String {
         let mut elves = parse_elves(input);
-        for round in 0..10 {
-            // first half
-            let proposals = generate_proposals(&elves, round);
-
-            // second half
-            apply_proposals(&mut elves, &proposals);
-        }
-        // display_elves(&elves);
+        spread(&mut elves, Some(10), &mut NoFrames);
         let min_x = elves.iter().map(|elf| elf.position.0).min().unwrap();
         let max_x = elves.iter().map(|elf| elf.position.0).max().unwrap();
         let min_y = elves.iter().map(|elf| elf.position.1).min().unwrap();
@@ -186,20 +179,48 @@ impl Day for Day23 {
 
     fn part_2(&self, input: &str) -> String {
         let mut elves = parse_elves(input);
-        let mut round = 0;
-        loop {
-            // first half
-            let proposals = generate_proposals(&elves, round);
-
-            // second half
-            let moved = apply_proposals(&mut elves, &proposals);
-            if !moved {
-                break;
-            }
-            round += 1;
+        spread(&mut elves, None, &mut NoFrames).add(1).to_string()
+    }
+
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        Some(self)
+    }
+
+    fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+        Some(lint::grid(1, input, ".#"))
+    }
+}
+
+impl Visualize for Day23 {
+    fn visualize(&self, input: &str, sink: &mut dyn FrameSink) {
+        let mut elves = parse_elves(input);
+        spread(&mut elves, None, sink);
+    }
+}
+
+/// Runs `limit` rounds, or until no elf moves without a limit, returning the index of the last round.
+fn spread<S: FrameSink + ?Sized>(elves: &mut [Elf], limit: Option<usize>, sink: &mut S) -> usize {
+    let mut round = 0;
+    loop {
+        if sink.is_recording() {
+            sink.emit(elves_frame(elves).with_caption(format!("round {round}")));
+        }
+        if limit == Some(round) {
+            break;
         }
-        round.add(1).to_string()
+        let _span = trace_span!("round", round).entered();
+        // first half
+        let proposals = generate_proposals(elves, round);
+
+        // second half
+        let moved = apply_proposals(elves, &proposals);
+        trace!(proposals = proposals.len(), moved);
+        if !moved && limit.is_none() {
+            break;
+        }
+        round += 1;
     }
+    round
 }
 
 fn parse_elves(input: &str) -> Vec<Elf> {
@@ -215,20 +236,19 @@ fn parse_elves(input: &str) -> Vec<Elf> {
     elves
 }
 
-fn display_elves(elves: &FxHashMap<usize, Elf>) {
-    let min_x = elves.values().map(|elf| elf.position.0).min().unwrap();
-    let max_x = elves.values().map(|elf| elf.position.0).max().unwrap();
-    let min_y = elves.values().map(|elf| elf.position.1).min().unwrap();
-    let max_y = elves.values().map(|elf| elf.position.1).max().unwrap();
-
-    for y in min_y..=max_y {
-        for x in min_x..=max_x {
-            if elves.values().any(|elf| elf.position == Point(x, y)) {
-                print!("#");
-            } else {
-                print!(".");
-            }
-        }
-        println!();
+fn elves_frame(elves: &[Elf]) -> Frame {
+    let min_x = elves.iter().map(|elf| elf.position.0).min().unwrap();
+    let max_x = elves.iter().map(|elf| elf.position.0).max().unwrap();
+    let min_y = elves.iter().map(|elf| elf.position.1).min().unwrap();
+    let max_y = elves.iter().map(|elf| elf.position.1).max().unwrap();
+
+    let width = min_x.abs_diff(max_x) + 1;
+    let height = min_y.abs_diff(max_y) + 1;
+    let mut frame = Frame::new(vec![vec!['.'; width]; height]);
+    for elf in elves {
+        let x = elf.position.0.abs_diff(min_x);
+        let y = elf.position.1.abs_diff(min_y);
+        frame.overlay((x, y), '#', Highlight::Primary);
     }
+    frame
 }
@@ -1,11 +1,10 @@
 use std::{collections::BinaryHeap, fmt::Display};
 
-#[cfg(feature = "visualize")]
-use crossterm::{cursor, execute, terminal, ExecutableCommand};
-#[cfg(feature = "visualize")]
-use std::io::{stdout, Write};
+use tracing::{debug, debug_span};
 
 use super::Day;
+use crate::lint::{self, Problem};
+use crate::visualize::{Frame, FrameSink, Highlight, NoFrames, Visualize};
 
 const MAX_STATES: usize = 50;
 
@@ -218,12 +217,20 @@ impl Display for Valley {
     }
 }
 
-#[cfg(not(feature = "visualize"))]
-fn traverse(start: Point, target: Point, valley: &mut Valley) -> usize {
+fn traverse<S: FrameSink + ?Sized>(
+    start: Point,
+    target: Point,
+    valley: &mut Valley,
+    sink: &mut S,
+) -> usize {
     let mut minutes = 0;
     let mut queue: BinaryHeap<State> = BinaryHeap::new();
     queue.push(State::new(start, target));
     'outer: loop {
+        if sink.is_recording() {
+            let occupied: Vec<Point> = queue.iter().map(|s| s.point).collect();
+            sink.emit(valley_frame(valley, &occupied, minutes));
+        }
         valley.simulate_next();
         let mut best_states = Vec::new();
         while best_states.len() < MAX_STATES && !queue.is_empty() {
@@ -249,96 +256,86 @@ fn traverse(start: Point, target: Point, valley: &mut Valley) -> usize {
         }
         minutes += 1;
     }
+    if sink.is_recording() {
+        sink.emit(valley_frame(valley, &[target], minutes));
+    }
     minutes
 }
 
+fn valley_frame(valley: &Valley, occupied: &[Point], minutes: usize) -> Frame {
+    let mut frame = Frame::from_text(&valley.to_string()).with_caption(format!("minute {minutes}"));
+    for &point in occupied {
+        frame.overlay(point, 'E', Highlight::Primary);
+    }
+    frame
+}
+
+fn there_and_back<S: FrameSink + ?Sized>(input: &str, sink: &mut S) -> usize {
+    let mut valley = Valley::new(input);
+    let there = debug_span!("leg", leg = "there")
+        .in_scope(|| traverse(valley.start(), valley.end(), &mut valley, sink));
+    let back = debug_span!("leg", leg = "back")
+        .in_scope(|| 1 + traverse(valley.end(), valley.start(), &mut valley, sink));
+    let there_again = debug_span!("leg", leg = "there again")
+        .in_scope(|| 1 + traverse(valley.start(), valley.end(), &mut valley, sink));
+    debug!(there, back, there_again, "minutes per leg");
+    there + back + there_again
+}
+
 pub struct Day24;
 
 impl Day for Day24 {
     fn part_1(&self, input: &str) -> String {
-        #[cfg(feature = "visualize")]
-        return String::new();
         let mut valley = Valley::new(input);
-        traverse(valley.start(), valley.end(), &mut valley).to_string()
+        traverse(valley.start(), valley.end(), &mut valley, &mut NoFrames).to_string()
     }
 
     fn part_2(&self, input: &str) -> String {
-        #[cfg(feature = "visualize")]
-        {
-            let mut stdout = stdout();
-            execute!(
-                stdout,
-                terminal::Clear(terminal::ClearType::All),
-                cursor::MoveToColumn(0),
-                cursor::MoveToRow(0),
-                cursor::Hide
-            )
-            .unwrap();
-        }
+        there_and_back(input, &mut NoFrames).to_string()
+    }
 
-        let mut valley = Valley::new(input);
-        let there = traverse(valley.start(), valley.end(), &mut valley);
-        let back = 1 + traverse(valley.end(), valley.start(), &mut valley);
-        let there_again = 1 + traverse(valley.start(), valley.end(), &mut valley);
-        let total = there + back + there_again;
-        total.to_string()
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        Some(self)
     }
-}
 
-#[cfg(feature = "visualize")]
-fn display_all(valley: &Valley, occupied: &[Point]) {
-    use owo_colors::OwoColorize;
-
-    let s = valley.to_string();
-    for (y, line) in s.lines().enumerate() {
-        for (x, tile) in line.chars().enumerate() {
-            if occupied.contains(&(x, y)) {
-                print!("{}", "E".fg_rgb::<166, 209, 137>());
-            } else {
-                print!("{}", tile.fg_rgb::<181, 191, 226>());
+    fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+        let mut problems = lint::grid(1, input, "#.<>^v");
+        problems.extend(lint::lines(1, input, |row| {
+            match (row.starts_with('#'), row.ends_with('#')) {
+                (true, true) => Ok(()),
+                _ => Err(String::from("expected walls at both ends")),
             }
-        }
-        println!();
+        }));
+        problems.sort_by_key(|problem| problem.line);
+        Some(problems)
     }
 }
 
-#[cfg(feature = "visualize")]
-fn traverse(start: Point, target: Point, valley: &mut Valley) -> usize {
-    let mut stdout = stdout();
-    let mut minutes = 0;
-    let mut queue: BinaryHeap<State> = BinaryHeap::new();
-    queue.push(State::new(start, target));
-    'outer: loop {
-        execute!(stdout, cursor::MoveToColumn(0), cursor::MoveToRow(0)).unwrap();
-        display_all(&valley, &queue.iter().map(|s| s.point).collect::<Vec<_>>());
+impl Visualize for Day24 {
+    fn visualize(&self, input: &str, sink: &mut dyn FrameSink) {
+        there_and_back(input, sink);
+    }
+}
+
+#[cfg(test)]
+mod day_24_tests {
+    use super::*;
+    use crate::days::SNAPSHOTS;
+    use crate::snapshot::assert_snapshot;
+
+    const VALLEY: &str = "#.######
+#>>.<^<#
+#.<..<<#
+#>v.><>#
+#<^v^^>#
+######.#
+";
+
+    #[test]
+    fn valley_renders() {
+        let mut valley = Valley::new(VALLEY);
+        assert_snapshot(SNAPSHOTS, "day_24_valley_start", &valley.to_string());
         valley.simulate_next();
-        let mut best_states = Vec::new();
-        while best_states.len() < MAX_STATES && !queue.is_empty() {
-            let state = queue.pop().unwrap();
-            if state.target == state.point {
-                break 'outer;
-            }
-            if best_states.contains(&state) {
-                continue;
-            }
-            best_states.push(state);
-        }
-        if best_states.is_empty() {
-            break;
-        }
-        queue.clear();
-        for state in best_states {
-            for new_point in valley.valid_moves_at(state.point) {
-                let mut state = state;
-                state.point = new_point;
-                queue.push(state);
-            }
-        }
-        minutes += 1;
-        std::thread::sleep(std::time::Duration::from_millis(100));
+        assert_snapshot(SNAPSHOTS, "day_24_valley_minute_1", &valley.to_string());
     }
-    execute!(stdout, cursor::MoveToColumn(0), cursor::MoveToRow(0)).unwrap();
-    display_all(&valley, &[target]);
-    std::thread::sleep(std::time::Duration::from_millis(500));
-    minutes
 }
@@ -1,6 +1,8 @@
 use std::{iter::Sum, str::FromStr};
 
 use super::Day;
+use crate::generate::Rng;
+use crate::lint::{self, Problem};
 
 #[derive(Debug)]
 struct ParseSNAFUError;
@@ -79,6 +81,28 @@ impl Day for Day25 {
     fn part_2(&self, _input: &str) -> String {
         "No Part 2.".to_string()
     }
+
+    fn lint(&self, input: &str) -> Option<Vec<Problem>> {
+        Some(lint::lines(1, input, |number| {
+            match number.chars().find(|&digit| !"=-012".contains(digit)) {
+                Some(digit) => Err(format!("{digit:?} is not a SNAFU digit")),
+                None if number.is_empty() => Err(String::from("expected a number")),
+                None => Ok(()),
+            }
+        }))
+    }
+
+    fn generate(&self, rng: &mut Rng) -> Option<String> {
+        // leading with 1 or 2 keeps every number, and so the sum, positive
+        let numbers = (0..rng.range(1..=150)).map(|_| {
+            let digits = rng.range(0..=19);
+            let rest: String = (0..digits)
+                .map(|_| *rng.pick(&['=', '-', '0', '1', '2']))
+                .collect();
+            format!("{}{rest}\n", rng.pick(&['1', '2']))
+        });
+        Some(numbers.collect())
+    }
 }
 
 #[cfg(test)]
@@ -97,4 +121,16 @@ mod day_25_tests {
         assert_eq!(SNAFU(1257).to_string(), "20012");
         assert_eq!(SNAFU(1747).to_string(), "1=-0-2");
     }
+
+    #[test]
+    fn lints_snafu_digits() {
+        assert_eq!(Day25.lint("1=-0-2\n12111\n"), Some(vec![]));
+        assert_eq!(
+            Day25.lint("1=-0-2\n\n1=3\n"),
+            Some(vec![
+                Problem::new(2, "expected a number"),
+                Problem::new(3, "'3' is not a SNAFU digit"),
+            ])
+        );
+    }
 }
@@ -1,64 +1,47 @@
-use owo_colors::OwoColorize;
-use utils::load_input;
+use aoc_core::Year;
+pub use aoc_core::{DayRun, Solution, SolveError, Timings};
 
-pub mod days;
-mod utils;
+pub use aoc_core::{
+    alloc, bundle, cache, generate, history, isolate, lint, logging, matrix, minimize, oracle,
+    report, serve, snapshot, stream, theme, visualize, watch,
+};
 
-pub fn run_day(day: u8) {
-    println!("{}", format!("Day: {day}").fg_rgb::<198, 208, 245>());
+pub mod days;
+pub mod scaffold;
 
-    let input = load_input(day);
-    let day: Box<dyn days::Day> = match day {
-        1 => {
-            dbg!(days::day_01::run(&input));
-            Box::new(days::Day01)
-        }
-        2 => {
-            dbg!(days::day_02::run(&input));
-            Box::new(days::Day02)
-        }
-        3 => {
-            dbg!(days::day_03::run(&input));
-            Box::new(days::Day03)
-        }
-        4 => {
-            dbg!(days::day_04::run(&input));
-            Box::new(days::Day04)
-        }
-        5 => {
-            dbg!(days::day_05::run(&input));
-            Box::new(days::Day05)
-        }
-        6 => Box::new(days::Day06),
-        7 => Box::new(days::Day07),
-        8 => Box::new(days::Day08),
-        9 => Box::new(days::Day09),
-        10 => Box::new(days::Day10),
-        11 => Box::new(days::Day11),
-        12 => Box::new(days::Day12),
-        13 => Box::new(days::Day13),
-        14 => Box::new(days::Day14),
-        15 => Box::new(days::Day15),
-        16 => Box::new(days::Day16),
-        17 => Box::new(days::Day17),
-        18 => Box::new(days::Day18),
-        19 => Box::new(days::Day19),
-        20 => Box::new(days::Day20),
-        21 => Box::new(days::Day21),
-        22 => Box::new(days::Day22),
-        23 => Box::new(days::Day23),
-        24 => Box::new(days::Day24),
-        25 => Box::new(days::Day25),
-        _ => unreachable!(),
-    };
+pub static YEAR: Year = Year {
+    year: 2022,
+    get: days::get,
+    inputs: concat!(env!("CARGO_MANIFEST_DIR"), "/inputs"),
+    version: env!("CARGO_PKG_VERSION"),
+    cache: concat!(env!("CARGO_MANIFEST_DIR"), "/target/answers"),
+    bundle: concat!(env!("CARGO_MANIFEST_DIR"), "/inputs.bundle"),
+    history: concat!(env!("CARGO_MANIFEST_DIR"), "/history.jsonl"),
+    // empty unless built with `embed-inputs`
+    embedded: include!(concat!(env!("OUT_DIR"), "/embedded.rs")),
+};
 
-    let one = day.part_1(&input);
-    println!("{}", "- Part 1:".fg_rgb::<181, 191, 226>());
-    println!("{}\n", one.fg_rgb::<166, 209, 137>());
+/// Solves one part of a day for the input, returning the answer and how long it took.
+///
+/// Nothing is printed, read from disk or cached.
+///
+/// ```
+/// let input = "1000\n2000\n3000\n\n4000\n";
+/// let solution = aoc::solve(1, 1, input).unwrap();
+/// assert_eq!(solution.answer, "6000");
+///
+/// assert!(aoc::solve(1, 3, input).is_err());
+/// ```
+pub fn solve(day: u8, part: u8, input: &str) -> Result<Solution, SolveError> {
+    YEAR.solve(day, part, input)
+}
 
-    let two = day.part_2(&input);
-    println!("{}", "- Part 2:".fg_rgb::<181, 191, 226>());
-    println!("{}", two.fg_rgb::<166, 209, 137>());
+/// Loads the day's input and solves both parts, using the answer cache.
+pub fn solve_day(day: u8) -> DayRun {
+    aoc_core::solve_day(&YEAR, day)
+}
 
-    println!("{}", "------------------".fg_rgb::<115, 121, 148>());
+/// Solves the day from its input and prints the answers.
+pub fn run_day(day: u8) -> DayRun {
+    aoc_core::run_day(&YEAR, day)
 }
@@ -1,5 +1,557 @@
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use aoc::{
+    report::Format,
+    theme::{self, Role, Theme},
+    visualize::export::ExportOptions,
+};
+use aoc_core::Year;
+
+/// Every year the CLI can solve, picked with `--year`.
+static YEARS: &[&Year] = &[&aoc::YEAR];
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOCATOR: aoc::alloc::CountingAllocator = aoc::alloc::CountingAllocator;
 
 fn main() {
-    aoc::run_day(17);
-    // aoc::run_day(19);
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    // `-v`, `-vv` and `-vvv` show more of what the solvers are doing, `-q` less
+    let mut verbosity = 0;
+    args.retain(|arg| match arg.as_str() {
+        "--verbose" => {
+            verbosity += 1;
+            false
+        }
+        "--quiet" => {
+            verbosity -= 1;
+            false
+        }
+        flags
+            if flags.len() > 1
+                && flags.starts_with('-')
+                && flags[1..].chars().all(|c| c == 'v' || c == 'q') =>
+        {
+            for flag in flags[1..].chars() {
+                verbosity += if flag == 'v' { 1 } else { -1 };
+            }
+            false
+        }
+        _ => true,
+    });
+    aoc::logging::init(verbosity);
+    // `--theme <dark|light|monochrome>` overrides the theme detected from the environment
+    if let Some(index) = args.iter().position(|arg| arg == "--theme") {
+        let name = args.get(index + 1).cloned().unwrap_or_default();
+        let Some(chosen) = Theme::named(&name) else {
+            eprintln!("unknown theme `{name}`, expected dark, light or monochrome");
+            std::process::exit(2);
+        };
+        theme::set(chosen);
+        args.drain(index..(index + 2).min(args.len()));
+    }
+    // `--no-cache` solves again even when the answer is already known
+    if let Some(index) = args.iter().position(|arg| arg == "--no-cache") {
+        aoc::cache::set_enabled(false);
+        args.remove(index);
+    }
+    // `--year <year>` picks which year's days to run, defaulting to the latest
+    let mut year = YEARS[YEARS.len() - 1];
+    if let Some(index) = args.iter().position(|arg| arg == "--year") {
+        let chosen = args.get(index + 1).and_then(|chosen| chosen.parse().ok());
+        let Some(chosen) = chosen.and_then(|chosen| aoc_core::find_year(YEARS, chosen)) else {
+            let known: Vec<String> = YEARS.iter().map(|year| year.year.to_string()).collect();
+            eprintln!("unknown year, expected one of {}", known.join(", "));
+            std::process::exit(2);
+        };
+        year = chosen;
+        args.drain(index..(index + 2).min(args.len()));
+    }
+    match args.first().map(String::as_str) {
+        // `cargo run -- run [--isolate] [--memory MiB] [--time s] <days...>`
+        Some("run") => run(year, &args[1..]),
+        // how `run --isolate` and `oracle` solve each day, in a process of its own
+        Some(aoc::isolate::CHILD_COMMAND) => {
+            let day = args[1].parse().unwrap();
+            match (option(&args, "--part"), option(&args, "--input")) {
+                (Some(part), Some(input)) => {
+                    aoc::isolate::child_part(year, day, part.parse().unwrap(), Path::new(input));
+                }
+                _ => aoc::isolate::child(year, day),
+            }
+        }
+        // `cargo run -- new <day>`
+        Some("new") => {
+            let day = args
+                .get(1)
+                .and_then(|day| day.parse().ok())
+                .unwrap_or_else(|| {
+                    eprintln!("usage: new <day>");
+                    std::process::exit(2);
+                });
+            // days are only scaffolded into this crate, which registers one year
+            if year.year != aoc::YEAR.year {
+                eprintln!("new only scaffolds days for {}", aoc::YEAR.year);
+                std::process::exit(2);
+            }
+            match aoc::scaffold::new_day(Path::new(env!("CARGO_MANIFEST_DIR")), day) {
+                Ok(module) => println!("created {}", module.display()),
+                Err(error) => {
+                    eprintln!("{error}");
+                    std::process::exit(1);
+                }
+            }
+        }
+        // `cargo run --release -- serve [--port n]`, listening on localhost only
+        Some("serve") => {
+            let port: u16 = option(&args, "--port").map_or(2022, |port| port.parse().unwrap());
+            if let Err(error) = aoc::serve::serve(year, ("127.0.0.1", port)) {
+                eprintln!("{error}");
+                std::process::exit(1);
+            }
+        }
+        // `cargo run -- watch <day> [--input path]`
+        Some("watch") => {
+            let Some(day) = args.get(1).and_then(|day| day.parse().ok()) else {
+                eprintln!("usage: watch <day> [--input path]");
+                std::process::exit(2);
+            };
+            let path = option(&args, "--input").map_or_else(|| year.input_path(day), PathBuf::from);
+            // every change is solved again, so that the timings mean something
+            aoc::cache::set_enabled(false);
+            if let Err(error) = aoc::watch::watch(year, day, &path) {
+                eprintln!("{error}");
+                std::process::exit(1);
+            }
+        }
+        // `cargo run -- export <day> <path> [--delay ms] [--hold ms] [--scale n] [--palette colours]`
+        Some("export") => export(year, &args[1..]),
+        // `cargo run --release -- report [--format md|html] [--output path] [days...]`
+        Some("report") => report(year, &args[1..]),
+        // `cargo run --release -- stream <day> [--input path]`, for inputs too large to hold
+        Some("stream") => stream(year, &args[1..]),
+        // `cargo run --release -- matrix [days...]`, solving each set of inputs under `inputs/`
+        Some("matrix") => {
+            let days = days(year, &args[1..]);
+            let sets = aoc::matrix::find_sets(year).unwrap_or_else(|error| {
+                eprintln!("could not find input sets: {error}");
+                std::process::exit(1);
+            });
+            // every cell is solved again, so that the timings can be compared
+            aoc::cache::set_enabled(false);
+            let matrix = aoc::matrix::run(year, &sets, &days);
+            print!("{}", aoc::matrix::render(theme::current(), &matrix));
+            if matrix.has_failures() {
+                std::process::exit(1);
+            }
+        }
+        // `cargo run -- history [--threshold percent] [days...]`, flagging days slower than last time
+        Some("history") => history(year, &args[1..]),
+        // `cargo run --release -- minimize <day> <file> [--output path] [--time s]`
+        Some("minimize") => minimize(year, &args[1..]),
+        // `cargo run --release -- oracle '<command>' [--generated n] [--seed n] [--output dir] [days...]`,
+        // where the command may use `{day}`, `{part}` and `{input}`, and inputs only in the bundle
+        // are decrypted into the output directory while compared
+        Some("oracle") => oracle(year, &args[1..]),
+        // `cargo run -- lint <day> <file>`, checking the file against the day's input format
+        Some("lint") => lint(year, &args[1..]),
+        // `cargo run -- identify <file>`, guessing which day an input is for
+        Some("identify") => identify(year, &args[1..]),
+        // `cargo run -- bundle key`, `cargo run --release -- bundle create [--output path] [days...]`
+        Some("bundle") => bundle(year, &args[1..]),
+        // `cargo run --release -- check [days...]`, against the answers in the bundle
+        Some("check") => {
+            let days = days(year, &args[1..]);
+            // a cached answer would pass without the solver having run
+            aoc::cache::set_enabled(false);
+            if !aoc_core::check_days(year, &days) {
+                std::process::exit(1);
+            }
+        }
+        // `cargo run --features visualize -- <day> [frame delay in ms]`
+        #[cfg(feature = "visualize")]
+        Some(day) => {
+            let delay = args.get(1).map_or(100, |delay| delay.parse().unwrap());
+            aoc_core::visualize_day(year, day.parse().unwrap(), Duration::from_millis(delay));
+        }
+        _ => {
+            aoc::run_day(17);
+            // aoc::run_day(19);
+        }
+    }
+}
+
+fn run(year: &Year, args: &[String]) {
+    // `--isolate` solves each day in a child process, so that one crashing spares the rest
+    let mut args = args.to_vec();
+    let Some(index) = args.iter().position(|arg| arg == "--isolate") else {
+        if args.iter().any(|arg| arg == "--memory" || arg == "--time") {
+            eprintln!("--memory and --time only limit isolated runs");
+            eprintln!("usage: run [--isolate] [--memory MiB] [--time s] <days...>");
+            std::process::exit(2);
+        }
+        let runs = days(year, &args)
+            .into_iter()
+            .map(|day| aoc_core::run_day(year, day))
+            .collect();
+        record(year, runs);
+        return;
+    };
+    args.remove(index);
+    let limits = aoc::isolate::Limits {
+        memory: option(&args, "--memory").map(|mib| mib.parse::<u64>().unwrap() << 20),
+        time: option(&args, "--time").map(|secs| Duration::from_secs_f64(secs.parse().unwrap())),
+    };
+    let days = days(year, &args);
+    let outcomes = aoc::isolate::run_days(year, &days, &limits);
+    // days that failed have no run to record
+    let runs: Vec<_> = outcomes
+        .into_iter()
+        .filter_map(|outcome| match outcome {
+            aoc::isolate::Outcome::Solved(run) => Some(run),
+            aoc::isolate::Outcome::Failed(_) => None,
+        })
+        .collect();
+    let failed = runs.len() < days.len();
+    record(year, runs);
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+/// Appends the runs to the year's history, warning rather than failing when it cannot.
+fn record(year: &Year, runs: Vec<aoc_core::DayRun>) {
+    if let Err(error) = aoc::history::record(year, runs) {
+        eprintln!("could not record the run in {}: {error}", year.history);
+    }
+}
+
+fn export(year: &Year, args: &[String]) {
+    let [day, path, ..] = args else {
+        eprintln!(
+            "usage: export <day> <path> [--delay ms] [--hold ms] [--scale n] [--palette colours]"
+        );
+        std::process::exit(2);
+    };
+    let mut options = ExportOptions::default();
+    if let Some(delay) = option(args, "--delay") {
+        options.delay = Duration::from_millis(delay.parse().unwrap());
+    }
+    if let Some(hold) = option(args, "--hold") {
+        options.hold = Duration::from_millis(hold.parse().unwrap());
+    }
+    if let Some(scale) = option(args, "--scale") {
+        options.scale = scale.parse().unwrap();
+    }
+    if let Some(palette) = option(args, "--palette") {
+        options.palette = palette.parse().unwrap();
+    }
+    if let Err(error) = aoc_core::export_day(year, day.parse().unwrap(), Path::new(path), &options)
+    {
+        eprintln!("{error}");
+        std::process::exit(1);
+    }
+}
+
+fn report(year: &Year, args: &[String]) {
+    let output = option(args, "--output").unwrap_or("report.md");
+    let format = match option(args, "--format") {
+        Some(format) => format.parse().unwrap_or_else(|error| {
+            eprintln!("{error}");
+            std::process::exit(2);
+        }),
+        None => Format::from_extension(Path::new(output).extension().and_then(|e| e.to_str())),
+    };
+    let days = days(year, args);
+    if let Err(error) = aoc_core::report_days(year, &days, format, Path::new(output)) {
+        eprintln!("{error}");
+        std::process::exit(1);
+    }
+}
+
+fn stream(year: &Year, args: &[String]) {
+    let Some(day) = args.first().and_then(|day| day.parse().ok()) else {
+        eprintln!("usage: stream <day> [--input path]");
+        std::process::exit(2);
+    };
+    let Some(stream) = year.get(day).and_then(|solver| solver.streaming()) else {
+        eprintln!("day {day} cannot stream its input");
+        std::process::exit(2);
+    };
+    let path = option(args, "--input").map_or_else(|| year.input_path(day), PathBuf::from);
+    println!("{}", theme::paint(Role::Header, format!("Day: {day}")));
+    for part in 1..=2 {
+        match aoc::stream::solve_file(stream, part, &path) {
+            Ok(solution) => {
+                println!("{}", theme::paint(Role::Label, format!("- Part {part}:")));
+                println!("{}", theme::paint(Role::Answer, &solution.answer));
+                let time = format!("{:.2?}", solution.time);
+                println!("{}", theme::paint(Role::Timing, time));
+            }
+            Err(error) => {
+                eprintln!("could not stream {}: {error}", path.display());
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+fn history(year: &Year, args: &[String]) {
+    let threshold: f64 =
+        option(args, "--threshold").map_or(10.0, |percent| percent.parse().unwrap());
+    let entries = aoc::history::read(Path::new(year.history), year.year).unwrap_or_else(|error| {
+        eprintln!("could not read {}: {error}", year.history);
+        std::process::exit(1);
+    });
+    let days = days(year, args);
+    let threshold = threshold / 100.0;
+    print!(
+        "{}",
+        aoc::history::render(theme::current(), &entries, &days, threshold)
+    );
+    if !aoc::history::changes(&entries, &days, threshold).is_empty() {
+        std::process::exit(1);
+    }
+}
+
+fn minimize(year: &Year, args: &[String]) {
+    let [day, path, ..] = args else {
+        eprintln!("usage: minimize <day> <file> [--output path] [--time s]");
+        std::process::exit(2);
+    };
+    let day = day.parse().unwrap();
+    let Some(solver) = year.get(day) else {
+        eprintln!("there is no solver for day {day}");
+        std::process::exit(2);
+    };
+    let input = std::fs::read_to_string(path).unwrap_or_else(|error| {
+        eprintln!("could not read {path}: {error}");
+        std::process::exit(1);
+    });
+    // the search stops at the first try that runs longer than this
+    let limit = option(args, "--time").map_or(Duration::from_secs(5), |secs| {
+        Duration::from_secs_f64(secs.parse().unwrap())
+    });
+    let Some((failure, minimized)) = aoc::minimize::minimize_day(solver, &input, limit) else {
+        eprintln!("day {day} does not fail on {path}");
+        std::process::exit(1);
+    };
+    println!(
+        "{}",
+        theme::paint(Role::Error, format!("day {day} {failure}"))
+    );
+    let output = option(args, "--output").map_or_else(|| format!("{path}.min"), String::from);
+    if let Err(error) = std::fs::write(&output, &minimized.input) {
+        eprintln!("could not write {output}: {error}");
+        std::process::exit(1);
+    }
+    let size = |input: &str| format!("{} bytes, {} lines", input.len(), input.lines().count());
+    println!(
+        "shrank {} to {} in {} tries",
+        size(&input),
+        size(&minimized.input),
+        minimized.tries
+    );
+    if minimized.stopped {
+        let note = format!("stopped early, as a try ran past {limit:?}");
+        println!("{}", theme::paint(Role::Error, note));
+    }
+    println!("{}", theme::paint(Role::Answer, format!("wrote {output}")));
+}
+
+fn oracle(year: &Year, args: &[String]) {
+    let Some(template) = args.first() else {
+        eprintln!("usage: oracle '<command>' [--generated n] [--seed n] [--output dir] [days...]");
+        eprintln!(
+            "inputs only in the bundle are decrypted into the output directory while compared,"
+        );
+        eprintln!("and left there if the answers to them differ");
+        std::process::exit(2);
+    };
+    let generated = option(args, "--generated").map_or(10, |n| n.parse().unwrap());
+    let seed = option(args, "--seed").map_or(0, |seed| seed.parse().unwrap());
+    let output = option(args, "--output").unwrap_or("target/oracle");
+    let oracle = aoc::oracle::Oracle::new(template.as_str());
+    let days = days(year, &args[1..]);
+    let comparison = aoc::oracle::compare(year, &oracle, &days, generated, seed, Path::new(output))
+        .unwrap_or_else(|error| {
+            eprintln!("could not write inputs to {output}: {error}");
+            std::process::exit(1);
+        });
+    print!("{}", aoc::oracle::render(theme::current(), &comparison));
+    if !comparison.mismatches.is_empty() {
+        std::process::exit(1);
+    }
+}
+
+fn lint(year: &Year, args: &[String]) {
+    let [day, path, ..] = args else {
+        eprintln!("usage: lint <day> <file>");
+        std::process::exit(2);
+    };
+    let day = day.parse().unwrap();
+    let Some(solver) = year.get(day) else {
+        eprintln!("there is no solver for day {day}");
+        std::process::exit(2);
+    };
+    let input = std::fs::read_to_string(path).unwrap_or_else(|error| {
+        eprintln!("could not read {path}: {error}");
+        std::process::exit(1);
+    });
+    let Some(problems) = solver.lint(&input) else {
+        eprintln!("day {day} does not describe its input format");
+        std::process::exit(2);
+    };
+    for problem in &problems {
+        let message = format!("{path}:{}: {}", problem.line, problem.message);
+        println!("{}", theme::paint(Role::Error, message));
+    }
+    if !problems.is_empty() {
+        std::process::exit(1);
+    }
+    println!(
+        "{}",
+        theme::paint(Role::Answer, format!("{path} looks like day {day}"))
+    );
+}
+
+fn identify(year: &Year, args: &[String]) {
+    let Some(path) = args.first() else {
+        eprintln!("usage: identify <file>");
+        std::process::exit(2);
+    };
+    let input = std::fs::read_to_string(path).unwrap_or_else(|error| {
+        eprintln!("could not read {path}: {error}");
+        std::process::exit(1);
+    });
+    let candidates = aoc::lint::identify(year, &input);
+    let Some(best) = candidates.first() else {
+        println!("{}", theme::paint(Role::Error, "no day accepts this input"));
+        std::process::exit(1);
+    };
+    println!(
+        "{}",
+        theme::paint(Role::Header, "day  confidence  lines accepted")
+    );
+    for candidate in &candidates {
+        let row = format!(
+            "{: >3}  {: >9.1}%  {: >13.1}%",
+            candidate.day,
+            candidate.confidence * 100.0,
+            candidate.accepted * 100.0
+        );
+        println!("{}", theme::paint(Role::Answer, row));
+    }
+    if best.accepted == 1.0 && best.confidence > 0.5 {
+        let path = year.input_path(best.day);
+        println!("\nprobably {}", path.display());
+    }
+}
+
+fn bundle(year: &Year, args: &[String]) {
+    match args.first().map(String::as_str) {
+        Some("key") => println!("{}", aoc::bundle::generate_key()),
+        Some("create") => {
+            let key = aoc::bundle::key_from_env().unwrap_or_else(|error| {
+                eprintln!("{error}");
+                std::process::exit(2);
+            });
+            let output = option(args, "--output").unwrap_or(year.bundle);
+            let days = bundled_days(year, &args[1..]);
+            // the expected answers come from the solvers as they are, not from the cache
+            aoc::cache::set_enabled(false);
+            let written = aoc_core::bundle_days(year, &days)
+                .and_then(|bundle| bundle.write(Path::new(output), &key, year.year));
+            match written {
+                Ok(()) => println!("bundled {} days into {output}", days.len()),
+                Err(error) => {
+                    eprintln!("{error}");
+                    std::process::exit(1);
+                }
+            }
+        }
+        _ => {
+            eprintln!("usage: bundle key | bundle create [--output path] [days...]");
+            std::process::exit(2);
+        }
+    }
+}
+
+/// The days named in `args`, skipping options and their values, or every day if none are.
+fn days(year: &Year, args: &[String]) -> Vec<u8> {
+    let days = named_days(args);
+    if days.is_empty() {
+        year.days()
+    } else {
+        days
+    }
+}
+
+/// The days named in `args`, skipping options and their values.
+fn named_days(args: &[String]) -> Vec<u8> {
+    let mut days = Vec::new();
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        if arg.starts_with("--") {
+            rest.next();
+        } else {
+            days.push(arg.parse().unwrap());
+        }
+    }
+    days
+}
+
+/// The days `bundle create` bundles: those named in `args`, or else those with an input on disk.
+fn bundled_days(year: &Year, args: &[String]) -> Vec<u8> {
+    let mut days = named_days(args);
+    if days.is_empty() {
+        days = year.days();
+        days.retain(|&day| year.input_path(day).exists());
+    }
+    days
+}
+
+/// The value following `name`, if given.
+fn option<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == name)
+        .and_then(|index| args.get(index + 1))
+        .map(String::as_str)
+}
+
+#[cfg(test)]
+mod main_tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn skips_option_values_when_naming_days() {
+        assert_eq!(
+            named_days(&args(&["--output", "x.bundle", "3", "7"])),
+            [3, 7]
+        );
+        assert!(named_days(&args(&["--output", "x.bundle"])).is_empty());
+    }
+
+    #[test]
+    fn bundles_days_with_inputs_unless_others_are_named() {
+        let dir = std::env::temp_dir().join(format!("aoc_main_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("day_2.txt"), "A Y\n").unwrap();
+        let year = Year {
+            inputs: Box::leak(dir.to_string_lossy().into_owned().into_boxed_str()),
+            ..aoc::YEAR
+        };
+        let create = bundled_days(&year, &args(&["--output", "x.bundle"]));
+        let named = bundled_days(&year, &args(&["--output", "x.bundle", "4"]));
+        std::fs::remove_dir_all(dir).unwrap();
+        assert_eq!(create, [2]);
+        assert_eq!(named, [4]);
+    }
 }
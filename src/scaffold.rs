@@ -0,0 +1,263 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Width that rustfmt wraps the `criterion_group!` list at.
+const MAX_WIDTH: usize = 100;
+
+/// Generates the module for a new day and wires it into the registry and the benchmarks.
+///
+/// `root` is the crate directory. Returns the path of the new module.
+pub fn new_day(root: &Path, day: u8) -> io::Result<PathBuf> {
+    if !(1..=25).contains(&day) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("there is no day {day}"),
+        ));
+    }
+    let module = root.join(format!("src/days/day_{day:02}.rs"));
+    if module.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", module.display()),
+        ));
+    }
+    let registry = root.join("src/days.rs");
+    let bench = root.join("benches/complete.rs");
+    let registry_source = register(&fs::read_to_string(&registry)?, day);
+    let bench_source = add_bench(&fs::read_to_string(&bench)?, day);
+
+    fs::write(&module, module_source(day))?;
+    fs::write(registry, registry_source)?;
+    fs::write(bench, bench_source)?;
+    Ok(module)
+}
+
+fn module_source(day: u8) -> String {
+    format!(
+        r#"use super::Day;
+
+pub struct Day{day:02};
+
+impl Day for Day{day:02} {{
+    fn part_1(&self, input: &str) -> String {{
+        part_1(&parse_input(input)).to_string()
+    }}
+
+    fn part_2(&self, input: &str) -> String {{
+        part_2(&parse_input(input)).to_string()
+    }}
+}}
+
+pub fn run(input: &str) -> (u32, u32) {{
+    let parsed = parse_input(input);
+    (part_1(&parsed), part_2(&parsed))
+}}
+
+pub fn parse_input(input: &str) -> Vec<&str> {{
+    input.lines().collect()
+}}
+
+pub fn part_1(_input: &[&str]) -> u32 {{
+    todo!("day {day} part 1")
+}}
+
+pub fn part_2(_input: &[&str]) -> u32 {{
+    todo!("day {day} part 2")
+}}
+
+#[cfg(test)]
+mod day_{day:02}_tests {{
+    use super::*;
+
+    const EXAMPLE: &str = "";
+
+    #[test]
+    #[ignore = "needs the example from the puzzle"]
+    fn part_1_example() {{
+        assert_eq!(part_1(&parse_input(EXAMPLE)), 0);
+    }}
+
+    #[test]
+    #[ignore = "needs the example from the puzzle"]
+    fn part_2_example() {{
+        assert_eq!(part_2(&parse_input(EXAMPLE)), 0);
+    }}
+}}
+"#
+    )
+}
+
+/// Adds the `mod` and `pub use` lines and the match arm in `get` to `days.rs`.
+fn register(source: &str, day: u8) -> String {
+    let mut lines: Vec<String> = source.lines().map(String::from).collect();
+    insert_sorted(
+        &mut lines,
+        format!("pub mod day_{day:02};"),
+        |line| {
+            line.strip_prefix("pub mod ")
+                .or_else(|| line.strip_prefix("mod "))
+                .filter(|name| name.starts_with("day_"))
+                .map(|name| name.trim_end_matches(';').to_owned())
+        },
+        format!("day_{day:02}"),
+    );
+    insert_sorted(
+        &mut lines,
+        format!("pub use day_{day:02}::Day{day:02};"),
+        |line| {
+            line.strip_prefix("pub use day_")
+                .map(|rest| format!("day_{}", rest.split("::").next().unwrap()))
+        },
+        format!("day_{day:02}"),
+    );
+    insert_sorted(
+        &mut lines,
+        format!("        {day} => &Day{day:02},"),
+        |line| {
+            let (number, solver) = line.trim().split_once(" => &Day")?;
+            solver.ends_with(',').then(|| format!("{:0>2}", number))
+        },
+        format!("{day:02}"),
+    );
+    lines.join("\n") + "\n"
+}
+
+/// Adds a `bench_day!` line and the day's entry in `criterion_group!`.
+fn add_bench(source: &str, day: u8) -> String {
+    let mut lines: Vec<String> = source.lines().map(String::from).collect();
+    insert_sorted(
+        &mut lines,
+        format!("bench_day!({day:02});"),
+        |line| {
+            line.strip_prefix("bench_day!(")
+                .map(|rest| rest.trim_end_matches(");").to_owned())
+        },
+        format!("{day:02}"),
+    );
+
+    let Some(start) = lines.iter().position(|line| line == "criterion_group!(") else {
+        return lines.join("\n") + "\n";
+    };
+    let end = start + lines[start..].iter().position(|line| line == ");").unwrap();
+    let mut names: Vec<String> = lines[start + 1..end]
+        .join(" ")
+        .split(',')
+        .map(|name| name.trim().to_owned())
+        .filter(|name| !name.is_empty())
+        .collect();
+    let group = names.remove(0);
+    let name = format!("day_{day:02}");
+    if !names.contains(&name) {
+        names.push(name);
+        names.sort();
+    }
+    let mut wrapped = Vec::new();
+    let mut line = format!("    {group},");
+    for name in names {
+        if line.len() + name.len() + 2 > MAX_WIDTH {
+            wrapped.push(line);
+            line = format!("    {name},");
+        } else {
+            line = format!("{line} {name},");
+        }
+    }
+    wrapped.push(line.trim_end_matches(',').to_owned());
+    lines.splice(start + 1..end, wrapped);
+    lines.join("\n") + "\n"
+}
+
+/// Inserts `new` among the lines that `key` recognizes, keeping them ordered by key.
+///
+/// Does nothing when a line with the same key is already there.
+fn insert_sorted(
+    lines: &mut Vec<String>,
+    new: String,
+    key: impl Fn(&str) -> Option<String>,
+    new_key: String,
+) {
+    let keyed: Vec<(usize, String)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| key(line).map(|key| (index, key)))
+        .collect();
+    if keyed.iter().any(|(_, key)| *key == new_key) {
+        return;
+    }
+    let index = match keyed.iter().rev().find(|(_, key)| *key < new_key) {
+        Some((index, _)) => index + 1,
+        None => keyed.first().map_or(lines.len(), |(index, _)| *index),
+    };
+    lines.insert(index, new);
+}
+
+#[cfg(test)]
+mod scaffold_tests {
+    use super::*;
+
+    const DAYS: &str = "pub mod day_01;
+mod day_03;
+
+pub use day_01::Day01;
+pub use day_03::Day03;
+
+pub fn get(day: u8) -> Option<&'static dyn Day> {
+    let day: &'static dyn Day = match day {
+        1 => &Day01,
+        3 => &Day03,
+        _ => return None,
+    };
+    Some(day)
+}
+";
+
+    const BENCH: &str = "bench_day!(01);
+bench_day!(03);
+
+// bench_day_old!(02);
+
+criterion_group!(
+    complete, day_01, day_03
+);
+criterion_main!(complete);
+";
+
+    #[test]
+    fn registers_in_order() {
+        let registered = register(DAYS, 2);
+        assert!(registered.starts_with("pub mod day_01;\npub mod day_02;\nmod day_03;\n"));
+        assert!(registered
+            .contains("pub use day_01::Day01;\npub use day_02::Day02;\npub use day_03::Day03;"));
+        assert!(registered.contains("1 => &Day01,\n        2 => &Day02,\n        3 => &Day03,"));
+        assert_eq!(register(&registered, 2), registered);
+    }
+
+    #[test]
+    fn adds_benchmarks() {
+        let bench = add_bench(BENCH, 2);
+        assert!(bench.starts_with("bench_day!(01);\nbench_day!(02);\nbench_day!(03);\n"));
+        assert!(bench.contains("criterion_group!(\n    complete, day_01, day_02, day_03\n);"));
+    }
+
+    #[test]
+    fn wraps_the_group_like_rustfmt() {
+        let names: Vec<String> = (1..=24).map(|day| format!("day_{day:02}")).collect();
+        let bench = format!(
+            "bench_day!(01);\n\ncriterion_group!(\n    complete, {}\n);\n",
+            names.join(", ")
+        );
+        assert_eq!(
+            add_bench(&bench, 25),
+            "bench_day!(01);
+bench_day!(25);
+
+criterion_group!(
+    complete, day_01, day_02, day_03, day_04, day_05, day_06, day_07, day_08, day_09, day_10,
+    day_11, day_12, day_13, day_14, day_15, day_16, day_17, day_18, day_19, day_20, day_21, day_22,
+    day_23, day_24, day_25
+);
+"
+        );
+    }
+}